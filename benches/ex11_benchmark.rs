@@ -1,21 +1,38 @@
 #[macro_use]
 extern crate criterion;
 
-use advent_of_code::ex11;
-use criterion::Criterion;
+use advent_of_code::ex11::{self, SeatingRules};
+use criterion::{BenchmarkId, Criterion};
+
+const RULES: [(&str, SeatingRules); 2] = [
+    ("adjacent", SeatingRules::PART_ONE),
+    ("visible", SeatingRules::PART_TWO),
+];
 
 fn count_occupied_seats_benchmark_once_allocated(c: &mut Criterion) {
-    let data = ex11::get_data("data_files/ex11.txt").unwrap();
-    c.bench_function("Once allocated", |b| {
-        b.iter(|| ex11::count_occupied_seats(data.as_str()))
-    });
+    let data = std::fs::read_to_string("data_files/ex11.txt").unwrap();
+    let mut group = c.benchmark_group("Once allocated");
+
+    for (label, rules) in &RULES {
+        group.bench_with_input(BenchmarkId::from_parameter(label), rules, |b, rules| {
+            b.iter(|| ex11::count_occupied_seats(data.as_str(), rules))
+        });
+    }
+
+    group.finish();
 }
 
 fn count_occupied_seats_benchmark_result_board_creation(c: &mut Criterion) {
-    let data = ex11::get_data("data_files/ex11.txt").unwrap();
-    c.bench_function("New board", |b| {
-        b.iter(|| ex11::count_occupied_seats2(data.as_str()))
-    });
+    let data = std::fs::read_to_string("data_files/ex11.txt").unwrap();
+    let mut group = c.benchmark_group("New board");
+
+    for (label, rules) in &RULES {
+        group.bench_with_input(BenchmarkId::from_parameter(label), rules, |b, rules| {
+            b.iter(|| ex11::count_occupied_seats2(data.as_str(), rules))
+        });
+    }
+
+    group.finish();
 }
 
 criterion_group!(
@@ -4,6 +4,12 @@ extern crate criterion;
 use advent_of_code::ex13::*;
 use criterion::Criterion;
 
+fn get_data() -> (u32, Vec<u32>) {
+    let input = std::fs::read_to_string("data_files/ex13.txt").unwrap();
+    let notes = parse_notes(&input).unwrap();
+    (notes.arrival_time, notes.ids())
+}
+
 fn ex13_benchmark(c: &mut Criterion) {
     let data = get_data();
     c.bench_function("n^2", |b| b.iter(|| get_bus_mult_minutes(data.0, &data.1)));
@@ -23,5 +29,17 @@ fn ex13_benchmark3(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, ex13_benchmark, ex13_benchmark2, ex13_benchmark3);
+fn ex13_benchmark_part_two(c: &mut Criterion) {
+    let input = std::fs::read_to_string("data_files/ex13.txt").unwrap();
+    let notes = parse_notes(&input).unwrap();
+    c.bench_function("crt", |b| b.iter(|| earliest_timestamp(&notes)));
+}
+
+criterion_group!(
+    benches,
+    ex13_benchmark,
+    ex13_benchmark2,
+    ex13_benchmark3,
+    ex13_benchmark_part_two
+);
 criterion_main!(benches);
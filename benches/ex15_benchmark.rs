@@ -1,29 +1,43 @@
 #[macro_use]
 extern crate criterion;
 
-use advent_of_code::ex15::*;
-use advent_of_code::ex15b::*;
-use advent_of_code::ex15c::*;
-use aoc_utils::DaySolver;
-use criterion::Criterion;
-
-fn ex13_benchmark(c: &mut Criterion) {
-    c.bench_function("vector tuple", |b| {
-        b.iter(|| Day15VersionA::solution("0,3,6"))
-    });
-}
+use advent_of_code::ex15::{self, PART_ONE_TURN, PART_TWO_TURN};
+use advent_of_code::{ex15b, ex15c};
+use criterion::{BenchmarkId, Criterion};
+
+type Play = fn(&str, usize) -> anyhow::Result<u32>;
+
+const VARIANTS: [(&str, Play); 3] = [
+    ("vector tuple", ex15::play),
+    ("hash map", ex15b::play),
+    ("vector with capacity", ex15c::play),
+];
+
+fn ex15_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("part one");
 
-fn ex13_benchmark2(c: &mut Criterion) {
-    c.bench_function("hash map", |b| {
-        b.iter(|| Day15VersionB::solution("0,3,6"))
-    });
+    for (label, play) in &VARIANTS {
+        group.bench_with_input(BenchmarkId::new(*label, PART_ONE_TURN), &PART_ONE_TURN, |b, turn| {
+            b.iter(|| play("0,3,6", *turn))
+        });
+    }
+
+    group.finish();
 }
 
-fn ex13_benchmark3(c: &mut Criterion) {
-    c.bench_function("vector with capacity", |b| {
-        b.iter(|| Day15VersionC::solution("0,3,6"))
-    });
+/// The vector with tuple searches the whole vector every turn, 30 million turns take hours.
+fn ex15_benchmark_part_two(c: &mut Criterion) {
+    let mut group = c.benchmark_group("part two");
+    group.sample_size(10);
+
+    for (label, play) in &VARIANTS[1..] {
+        group.bench_with_input(BenchmarkId::new(*label, PART_TWO_TURN), &PART_TWO_TURN, |b, turn| {
+            b.iter(|| play("0,3,6", *turn))
+        });
+    }
+
+    group.finish();
 }
 
-criterion_group!(benches, ex13_benchmark, ex13_benchmark2, ex13_benchmark3);
+criterion_group!(benches, ex15_benchmark, ex15_benchmark_part_two);
 criterion_main!(benches);
@@ -1,8 +1,8 @@
 #[macro_use]
 extern crate criterion;
 
-use advent_of_code::ex6;
-use criterion::Criterion;
+use advent_of_code::ex6::{self, Aggregation};
+use criterion::{BenchmarkId, Criterion};
 
 fn get_data() -> String {
     "abc
@@ -23,30 +23,61 @@ fn get_data() -> String {
     .to_string()
 }
 
+const AGGREGATIONS: [(&str, Aggregation); 2] = [
+    ("union", Aggregation::Anyone),
+    ("intersection", Aggregation::Everyone),
+];
+
 fn count_answers_benchmark(c: &mut Criterion) {
     let data = get_data();
-    c.bench_function("HashSet", |b| {
-        b.iter(|| ex6::count_answers_bench(data.as_str()))
-    });
+    let mut group = c.benchmark_group("HashSet");
+    for (name, aggregation) in AGGREGATIONS {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(name),
+            &aggregation,
+            |b, aggregation| b.iter(|| ex6::count_answers_bench(data.as_str(), *aggregation)),
+        );
+    }
+    group.finish();
 }
 
 fn count_answers2_benchmark(c: &mut Criterion) {
     let data = get_data();
-    c.bench_function("Sort Dedup", |b| {
-        b.iter(|| ex6::count_answers2_bench(data.as_str()))
-    });
+    let mut group = c.benchmark_group("Sort Dedup");
+    for (name, aggregation) in AGGREGATIONS {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(name),
+            &aggregation,
+            |b, aggregation| b.iter(|| ex6::count_answers2_bench(data.as_str(), *aggregation)),
+        );
+    }
+    group.finish();
 }
 
 fn count_answers3_benchmark(c: &mut Criterion) {
     let data = get_data();
-    c.bench_function("Mapper", |b| {
-        b.iter(|| ex6::count_answers3_bench(data.as_str()))
-    });
+    let mut group = c.benchmark_group("Mapper");
+    for (name, aggregation) in AGGREGATIONS {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(name),
+            &aggregation,
+            |b, aggregation| b.iter(|| ex6::count_answers3_bench(data.as_str(), *aggregation)),
+        );
+    }
+    group.finish();
 }
 
 fn count_answers4_benchmark(c: &mut Criterion) {
     let data = get_data();
-    c.bench_function("Mentor", |b| b.iter(|| ex6::count_answers4(data.as_str())));
+    let mut group = c.benchmark_group("Mentor");
+    for (name, aggregation) in AGGREGATIONS {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(name),
+            &aggregation,
+            |b, aggregation| b.iter(|| ex6::count_answers4(data.as_str(), *aggregation)),
+        );
+    }
+    group.finish();
 }
 
 criterion_group!(
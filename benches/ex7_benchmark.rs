@@ -4,6 +4,7 @@ extern crate criterion;
 use std::fs::read_to_string;
 
 use advent_of_code::ex7::Day7;
+use advent_of_code::part::PartTwo;
 use aoc_utils::*;
 use criterion::Criterion;
 
@@ -12,6 +13,11 @@ fn ex7_benchmark(c: &mut Criterion) {
         let t = read_to_string("data_files/ex7.txt").unwrap();
         b.iter(|| Day7::solution(t.as_str()))
     });
+
+    c.bench_function("part two", |b| {
+        let t = read_to_string("data_files/ex7.txt").unwrap();
+        b.iter(|| Day7::solution_part_two(t.as_str()))
+    });
 }
 
 criterion_group!(benches, ex7_benchmark);
@@ -0,0 +1,75 @@
+#[macro_use]
+extern crate criterion;
+
+use advent_of_code::generators;
+use advent_of_code::part::Part;
+use advent_of_code::registry::{self, DayNum, SolverEntry};
+use criterion::{BenchmarkId, Criterion};
+
+const SEED: u64 = 2020;
+
+/// Synthetic input sizes, kept small enough for the slowest variant of given day and for
+/// every variant to solve them.
+fn synthetic_sizes(day: DayNum) -> &'static [usize] {
+    match day {
+        10 => &[50, 100],
+        11 => &[20, 90],
+        15 => &[3, 20],
+        17 => &[5, 8],
+        19 | 21 => &[50, 200],
+        20 => &[3],
+        22 => &[5, 25],
+        23 => &[9],
+        25 => &[10, 40],
+        _ => &[100, 1000],
+    }
+}
+
+fn inputs(day: DayNum, default_file: &str) -> Vec<(String, String)> {
+    let mut inputs = Vec::new();
+
+    if let Ok(input) = std::fs::read_to_string(default_file) {
+        inputs.push(("default".to_owned(), input));
+    }
+
+    for size in synthetic_sizes(day) {
+        if let Some(input) = generators::generate(day, SEED, *size) {
+            inputs.push((format!("synthetic {}", size), input));
+        }
+    }
+
+    inputs
+}
+
+fn registry_benchmark(c: &mut Criterion) {
+    let mut day_parts: Vec<(DayNum, Part)> = registry::SOLVERS
+        .iter()
+        .map(|entry| (entry.day, entry.part))
+        .collect();
+    day_parts.sort_unstable();
+    day_parts.dedup();
+
+    for (day, part) in day_parts {
+        let entries: Vec<&SolverEntry> = registry::find(Some(day), Some(part), None).collect();
+        let mut group = c.benchmark_group(format!("day {} part {}", day, part));
+
+        for (label, input) in inputs(day, entries[0].default_file) {
+            for entry in &entries {
+                if let Err(err) = (entry.solve)(&input) {
+                    panic!("day {} part {} [{}] on {}: {}", day, part, entry.variant, label, err);
+                }
+
+                group.bench_with_input(
+                    BenchmarkId::new(entry.variant, &label),
+                    &input,
+                    |b, input| b.iter(|| (entry.solve)(input)),
+                );
+            }
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, registry_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,125 @@
+use super::part::Part;
+use super::registry::{self, DayNum, SolverEntry};
+
+// -----------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct VariantOutput {
+    pub variant: &'static str,
+    pub result:  Result<String, String>,
+}
+
+/// Outputs of every variant of one day part for the same input, the first one is the reference.
+#[derive(Debug, PartialEq, Eq)]
+pub struct AgreementReport {
+    pub day:     DayNum,
+    pub part:    Part,
+    pub outputs: Vec<VariantOutput>,
+}
+
+impl AgreementReport {
+    pub fn reference(&self) -> &VariantOutput {
+        &self.outputs[0]
+    }
+
+    /// Variants which failed or returned something else than the reference variant.
+    pub fn disagreeing(&self) -> impl Iterator<Item = &VariantOutput> {
+        let reference = &self.reference().result;
+
+        self.outputs
+            .iter()
+            .filter(move |output| output.result.is_err() || output.result != *reference)
+    }
+
+    pub fn agrees(&self) -> bool {
+        self.disagreeing().next().is_none()
+    }
+}
+
+fn variants(day: DayNum, part: Part) -> Vec<&'static SolverEntry> {
+    registry::find(Some(day), Some(part), None).collect()
+}
+
+/// `None` when given day part has less than two variants to compare.
+pub fn check_variants(day: DayNum, part: Part, input: &str) -> Option<AgreementReport> {
+    let entries = variants(day, part);
+
+    if entries.len() < 2 {
+        return None;
+    }
+
+    let outputs = entries
+        .iter()
+        .map(|entry| VariantOutput {
+            variant: entry.variant,
+            result:  (entry.solve)(input).map_err(|err| err.to_string()),
+        })
+        .collect();
+
+    Some(AgreementReport { day, part, outputs })
+}
+
+/// Every day part with competing variants, each checked against its default file.
+pub fn check_default_files(day: Option<DayNum>) -> anyhow::Result<Vec<AgreementReport>> {
+    let mut reports: Vec<AgreementReport> = Vec::new();
+
+    for entry in registry::find(day, None, None) {
+        let already_checked = reports
+            .iter()
+            .any(|report| report.day == entry.day && report.part == entry.part);
+
+        if already_checked {
+            continue;
+        }
+
+        let input = std::fs::read_to_string(entry.default_file)?;
+
+        if let Some(report) = check_variants(entry.day, entry.part, &input) {
+            reports.push(report);
+        }
+    }
+
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn output(variant: &'static str, result: Result<&str, &str>) -> VariantOutput {
+        VariantOutput {
+            variant,
+            result: result.map(str::to_owned).map_err(str::to_owned),
+        }
+    }
+
+    #[test_case(vec![output("a", Ok("1")), output("b", Ok("1"))] => Vec::<&str>::new(); "all agree")]
+    #[test_case(vec![output("a", Ok("1")), output("b", Ok("2")), output("c", Ok("1"))] => vec!["b"]; "one differs")]
+    #[test_case(vec![output("a", Err("x")), output("b", Err("x"))] => vec!["a", "b"]; "errors never agree")]
+    fn agreement_disagreeing(outputs: Vec<VariantOutput>) -> Vec<&'static str> {
+        let report = AgreementReport {
+            day: 0,
+            part: Part::One,
+            outputs,
+        };
+
+        report.disagreeing().map(|output| output.variant).collect()
+    }
+
+    #[test_case(15, "1,3,2" => Some(true); "three variants")]
+    #[test_case(18, "1 + 2" => None; "single variant")]
+    fn agreement_check_variants(day: DayNum, input: &str) -> Option<bool> {
+        check_variants(day, Part::One, input).map(|report| report.agrees())
+    }
+
+    #[test]
+    fn agreement_default_files() {
+        let reports = check_default_files(None).unwrap();
+
+        assert!(reports.iter().any(|report| report.day == 25));
+        for report in reports {
+            assert!(report.agrees(), "{:?}", report);
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use std::fs::read_to_string;
+use thiserror::Error;
+
+use super::part::Part;
+use super::registry::{self, DayNum, SolverEntry};
+
+pub const DEFAULT_MANIFEST: &str = "data_files/answers.txt";
+
+const FIELDS_DELIMETER: char = '|';
+const COMMENT_MARK: char = '#';
+
+// -----------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ExpectedAnswer {
+    pub day:      DayNum,
+    pub part:     Part,
+    pub variant:  String,
+    pub input:    String,
+    pub expected: String,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ManifestError {
+    #[error("line {line}: expected 5 fields separated by `|`, found {found}")]
+    WrongFieldsNumber { line: usize, found: usize },
+
+    #[error("line {line}: invalid day `{text}`")]
+    InvalidDay { line: usize, text: String },
+
+    #[error("line {line}: invalid part `{text}`")]
+    InvalidPart { line: usize, text: String },
+}
+
+// 15 | 1 | hash map | data_files/ex15.txt | 436
+fn parse_line(line: usize, s: &str) -> Result<ExpectedAnswer, ManifestError> {
+    let fields: Vec<&str> = s.split(FIELDS_DELIMETER).map(|field| field.trim()).collect();
+
+    let [day, part, variant, input, expected] = fields[..] else {
+        return Err(ManifestError::WrongFieldsNumber {
+            line,
+            found: fields.len(),
+        });
+    };
+
+    Ok(ExpectedAnswer {
+        day:      day.parse::<DayNum>().map_err(|_| ManifestError::InvalidDay {
+            line,
+            text: day.to_owned(),
+        })?,
+        part:     part.parse::<Part>().map_err(|_| ManifestError::InvalidPart {
+            line,
+            text: part.to_owned(),
+        })?,
+        variant:  variant.to_owned(),
+        input:    input.to_owned(),
+        expected: expected.to_owned(),
+    })
+}
+
+fn is_skipped(line: &str) -> bool {
+    let line = line.trim();
+    line.is_empty() || line.starts_with(COMMENT_MARK)
+}
+
+/// Skips empty lines and lines starting with `#`, line numbers in errors start from 1.
+pub fn parse_manifest(s: &str) -> Result<Vec<ExpectedAnswer>, ManifestError> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !is_skipped(line))
+        .map(|(line_num, line)| parse_line(line_num + 1, line))
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Mismatch(String),
+    Fail(String),
+    NoSolver,
+    NoAnswer,
+}
+
+impl CheckStatus {
+    /// Only missing answers are tolerated, a manifest line without a solver is a failure.
+    pub fn is_ok(&self) -> bool {
+        matches!(self, CheckStatus::Pass | CheckStatus::NoAnswer)
+    }
+}
+
+#[derive(Debug)]
+pub struct CheckReport {
+    pub day:     DayNum,
+    pub part:    Part,
+    pub variant: String,
+    pub input:   String,
+    pub status:  CheckStatus,
+}
+
+pub fn check_answer(answer: &ExpectedAnswer) -> CheckStatus {
+    let mut entries = registry::find(
+        Some(answer.day),
+        Some(answer.part),
+        Some(answer.variant.as_str()),
+    );
+
+    let Some(entry) = entries.next() else {
+        return CheckStatus::NoSolver;
+    };
+
+    let result = read_to_string(&answer.input)
+        .map_err(anyhow::Error::from)
+        .and_then(|input| (entry.solve)(&input));
+
+    match result {
+        Ok(output) if output == answer.expected => CheckStatus::Pass,
+        Ok(output) => CheckStatus::Mismatch(output),
+        Err(err) => CheckStatus::Fail(err.to_string()),
+    }
+}
+
+/// Checks every answer of the manifest and lists registered solvers the manifest does not cover.
+pub fn check_all(answers: &[ExpectedAnswer]) -> Vec<CheckReport> {
+    let mut reports: Vec<CheckReport> = answers
+        .iter()
+        .map(|answer| CheckReport {
+            day:     answer.day,
+            part:    answer.part,
+            variant: answer.variant.clone(),
+            input:   answer.input.clone(),
+            status:  check_answer(answer),
+        })
+        .collect();
+
+    let is_covered = |entry: &SolverEntry| {
+        answers.iter().any(|answer| {
+            answer.day == entry.day && answer.part == entry.part && answer.variant == entry.variant
+        })
+    };
+
+    reports.extend(
+        registry::SOLVERS
+            .iter()
+            .filter(|entry| !is_covered(entry))
+            .map(|entry| CheckReport {
+                day:     entry.day,
+                part:    entry.part,
+                variant: entry.variant.to_owned(),
+                input:   entry.default_file.to_owned(),
+                status:  CheckStatus::NoAnswer,
+            }),
+    );
+
+    reports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn answer(day: DayNum, variant: &str, input: &str, expected: &str) -> ExpectedAnswer {
+        ExpectedAnswer {
+            day,
+            part:     Part::One,
+            variant:  variant.to_owned(),
+            input:    input.to_owned(),
+            expected: expected.to_owned(),
+        }
+    }
+
+    #[test_case("15 | 1 | hash map | data_files/ex15.txt | 436" => Ok(answer(15, "hash map", "data_files/ex15.txt", "436")); "valid line")]
+    #[test_case("15 | 1 | hash map | data_files/ex15.txt" => Err(ManifestError::WrongFieldsNumber{line: 1, found: 4}); "missing field")]
+    #[test_case("x | 1 | base | data_files/ex15.txt | 436" => Err(ManifestError::InvalidDay{line: 1, text: "x".to_owned()}); "invalid day")]
+    #[test_case("15 | 3 | base | data_files/ex15.txt | 436" => Err(ManifestError::InvalidPart{line: 1, text: "3".to_owned()}); "invalid part")]
+    fn answers_parse_line(s: &str) -> Result<ExpectedAnswer, ManifestError> {
+        parse_line(1, s)
+    }
+
+    #[test_case("# comment\n\n18 | 1 | base | data_files/ex18.txt | 26335" => Ok(1); "comments and empty lines skipped")]
+    #[test_case("# comment\n\n18 | 1 | base" => Err(ManifestError::WrongFieldsNumber{line: 3, found: 3}); "line number counts skipped lines")]
+    fn answers_parse_manifest(s: &str) -> Result<usize, ManifestError> {
+        Ok(parse_manifest(s)?.len())
+    }
+
+    #[test_case(answer(18, "base", "data_files/ex18.txt", "26335") => CheckStatus::Pass; "pass")]
+    #[test_case(answer(18, "base", "data_files/ex18.txt", "1") => CheckStatus::Mismatch("26335".to_owned()); "mismatch")]
+    #[test_case(answer(18, "unknown", "data_files/ex18.txt", "26335") => CheckStatus::NoSolver; "no solver")]
+    fn answers_check_answer(answer: ExpectedAnswer) -> CheckStatus {
+        check_answer(&answer)
+    }
+
+    #[test]
+    fn answers_check_answer_no_file() {
+        let status = check_answer(&answer(18, "base", "aaa", "26335"));
+        assert!(matches!(status, CheckStatus::Fail(_)));
+    }
+
+    #[test]
+    fn answers_check_all_unknown_variant_fails() {
+        let manifest = parse_manifest("18 | 1 | unknown | data_files/ex18.txt | 26335").unwrap();
+        let reports = check_all(&manifest);
+
+        assert_eq!(reports[0].status, CheckStatus::NoSolver);
+        assert!(!reports[0].status.is_ok());
+    }
+
+    #[test]
+    fn answers_default_manifest_passes() {
+        let manifest = read_to_string(DEFAULT_MANIFEST).unwrap();
+        let reports = check_all(&parse_manifest(&manifest).unwrap());
+
+        for report in reports {
+            assert!(
+                report.status.is_ok(),
+                "day {} part {} [{}]: {:?}",
+                report.day,
+                report.part,
+                report.variant,
+                report.status
+            );
+        }
+    }
+}
@@ -0,0 +1,337 @@
+use advent_of_code::agreement::{self, AgreementReport};
+use advent_of_code::answers::{self, CheckStatus};
+use advent_of_code::ex9;
+use advent_of_code::generators;
+use advent_of_code::input::InputSource;
+use advent_of_code::measurements::{self, Comparison, Measurement};
+use advent_of_code::part::Part;
+use advent_of_code::registry::{self, DayNum, SolverEntry};
+use anyhow::{anyhow, bail};
+
+const USAGE: &str = "usage:
+    aoc list
+    aoc run <day|all> [--part <1|2>] [--variant <label>] [--input <path|->] [--iterations <n>]
+            [--format <text|csv>] [--preamble <n>]
+    aoc compare <before.csv> <after.csv>
+    aoc check [manifest]
+    aoc agree <day|all> [--part <1|2>] [--input <path|-> | --seed <n> [--size <n>]]
+    aoc gen <day> [--seed <n>] [--size <n>]";
+
+const DEFAULT_SEED: u64 = 2020;
+const DEFAULT_SIZE: usize = 100;
+
+fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("list") => list(),
+        Some("run") => run(&args[1..])?,
+        Some("compare") => compare(&args[1..])?,
+        Some("check") => check(&args[1..])?,
+        Some("agree") => agree(&args[1..])?,
+        Some("gen") => gen(&args[1..])?,
+        _ => println!("{}", USAGE),
+    }
+
+    Ok(())
+}
+
+fn list() {
+    for entry in registry::SOLVERS {
+        println!(
+            "day {:>2}  part {}  {:<22} {}",
+            entry.day, entry.part, entry.variant, entry.default_file
+        );
+    }
+}
+
+fn run(args: &[String]) -> anyhow::Result<()> {
+    let day = day_argument(args)?;
+    let part = part_option(args)?;
+    let variant = option_value(args, "--variant")?;
+    let iterations = match option_value(args, "--iterations")? {
+        Some(iterations) => iterations
+            .parse::<u32>()
+            .map_err(|_| anyhow!("invalid iterations `{}`", iterations))?,
+        None => 1,
+    };
+    let csv = match option_value(args, "--format")? {
+        None | Some("text") => false,
+        Some("csv") => true,
+        Some(format) => bail!("unknown format `{}`", format),
+    };
+
+    let preamble = match option_value(args, "--preamble")? {
+        Some(_) if day != Some(9) => bail!("`--preamble` applies only to day 9"),
+        Some(preamble) => Some(
+            preamble
+                .parse::<usize>()
+                .map_err(|_| anyhow!("invalid preamble `{}`", preamble))?,
+        ),
+        None => None,
+    };
+
+    let selected: Vec<&SolverEntry> = registry::find(day, part, variant).collect();
+
+    if selected.is_empty() {
+        bail!("no solver registered for given day, part and variant");
+    }
+
+    // read once, stdin can not be read again for the next variant
+    let input = match input_option(args)? {
+        Some(_) if day.is_none() => bail!("`--input` requires a single day"),
+        Some(source) => Some((source.to_string(), source.read()?)),
+        None => None,
+    };
+
+    if csv {
+        println!("{}", measurements::CSV_HEADER);
+    }
+
+    for entry in selected {
+        let solve = |input: &str| match (preamble, entry.part) {
+            (None, _) => (entry.solve)(input),
+            (Some(preamble), Part::One) => Ok(ex9::first_invalid(input, preamble)?.to_string()),
+            (Some(preamble), Part::Two) => {
+                Ok(ex9::encryption_weakness(input, preamble)?.to_string())
+            },
+        };
+
+        let measurement = match &input {
+            Some((name, input)) => {
+                measurements::measure_with(entry, solve, name, input, iterations)?
+            },
+            None => {
+                let input = InputSource::from(entry.default_file).read()?;
+                measurements::measure_with(entry, solve, entry.default_file, &input, iterations)?
+            },
+        };
+
+        if csv {
+            println!("{}", measurement);
+        } else {
+            print_measurement(&measurement);
+        }
+    }
+
+    Ok(())
+}
+
+fn print_measurement(measurement: &Measurement) {
+    println!(
+        "day {} part {} [{}]: {} (took {:?})",
+        measurement.day,
+        measurement.part,
+        measurement.variant,
+        measurement.result,
+        measurement.wall_time
+    );
+}
+
+fn compare(args: &[String]) -> anyhow::Result<()> {
+    let [before, after] = args else {
+        bail!("expected two results files\n{}", USAGE);
+    };
+
+    let comparisons = measurements::compare(
+        &measurements::parse_results(&std::fs::read_to_string(before)?)?,
+        &measurements::parse_results(&std::fs::read_to_string(after)?)?,
+    );
+
+    println!(
+        "{:>3} {:>4}  {:<22} {:<28} {:>14} {:>14} {:>8}",
+        "day", "part", "variant", "input", "before", "after", "change"
+    );
+
+    for comparison in &comparisons {
+        print_comparison(comparison);
+    }
+
+    Ok(())
+}
+
+fn print_comparison(comparison: &Comparison) {
+    let time = |time: Option<std::time::Duration>| {
+        time.map_or("-".to_owned(), |time| format!("{:?}", time))
+    };
+    let change = comparison
+        .change_percent()
+        .map_or("-".to_owned(), |change| format!("{:+.1}%", change));
+    let flag = if comparison.result_changed {
+        "  <-- RESULT CHANGED"
+    } else {
+        ""
+    };
+
+    println!(
+        "{:>3} {:>4}  {:<22} {:<28} {:>14} {:>14} {:>8}{}",
+        comparison.day,
+        comparison.part,
+        comparison.variant,
+        comparison.input,
+        time(comparison.before),
+        time(comparison.after),
+        change,
+        flag
+    );
+}
+
+fn check(args: &[String]) -> anyhow::Result<()> {
+    let manifest_path = args
+        .first()
+        .map(String::as_str)
+        .unwrap_or(answers::DEFAULT_MANIFEST);
+
+    let manifest = std::fs::read_to_string(manifest_path)?;
+    let reports = answers::check_all(&answers::parse_manifest(&manifest)?);
+
+    println!(
+        "{:>3} {:>4}  {:<22} {:<28} status",
+        "day", "part", "variant", "input"
+    );
+
+    for report in &reports {
+        let status = match &report.status {
+            CheckStatus::Pass => "pass".to_owned(),
+            CheckStatus::Mismatch(output) => format!("MISMATCH (got {})", output),
+            CheckStatus::Fail(err) => format!("FAIL ({})", err),
+            CheckStatus::NoSolver => "no solver registered".to_owned(),
+            CheckStatus::NoAnswer => "no expected answer".to_owned(),
+        };
+
+        println!(
+            "{:>3} {:>4}  {:<22} {:<28} {}",
+            report.day, report.part, report.variant, report.input, status
+        );
+    }
+
+    let failed = reports
+        .iter()
+        .filter(|report| !report.status.is_ok())
+        .count();
+
+    if failed > 0 {
+        bail!("{} of {} checks failed", failed, reports.len());
+    }
+
+    Ok(())
+}
+
+fn agree(args: &[String]) -> anyhow::Result<()> {
+    let day = day_argument(args)?;
+    let part = part_option(args)?;
+
+    let input = match (input_option(args)?, option_value(args, "--seed")?) {
+        (Some(_), Some(_)) => bail!("`--input` and `--seed` can not be used together"),
+        (Some(source), None) => Some(source.read()?),
+        (None, Some(_)) => Some(generated_input(day, args)?),
+        (None, None) => None,
+    };
+
+    let reports: Vec<AgreementReport> = match input {
+        None => agreement::check_default_files(day)?
+            .into_iter()
+            .filter(|report| part.is_none_or(|part| report.part == part))
+            .collect(),
+        Some(input) => {
+            let Some(day) = day else {
+                bail!("`--input` and `--seed` require a single day");
+            };
+
+            [Part::One, Part::Two]
+                .into_iter()
+                .filter(|checked| part.is_none_or(|part| *checked == part))
+                .filter_map(|checked| agreement::check_variants(day, checked, &input))
+                .collect()
+        },
+    };
+
+    if reports.is_empty() {
+        bail!("no day with more than one variant selected");
+    }
+
+    for report in &reports {
+        println!("day {} part {}", report.day, report.part);
+
+        for output in &report.outputs {
+            let result = match &output.result {
+                Ok(value) => value.to_owned(),
+                Err(err) => format!("error: {}", err),
+            };
+            let flag = if report.disagreeing().any(|other| other.variant == output.variant) {
+                "  <-- DISAGREES"
+            } else {
+                ""
+            };
+
+            println!("    {:<22} {}{}", output.variant, result, flag);
+        }
+    }
+
+    let disagreements = reports.iter().filter(|report| !report.agrees()).count();
+
+    if disagreements > 0 {
+        bail!("variants disagree in {} of {} day parts", disagreements, reports.len());
+    }
+
+    Ok(())
+}
+
+fn gen(args: &[String]) -> anyhow::Result<()> {
+    let day = day_argument(args)?;
+    println!("{}", generated_input(day, args)?);
+
+    Ok(())
+}
+
+fn generated_input(day: Option<DayNum>, args: &[String]) -> anyhow::Result<String> {
+    let Some(day) = day else {
+        bail!("input can be generated only for a single day");
+    };
+
+    let seed = match option_value(args, "--seed")? {
+        Some(seed) => seed.parse::<u64>().map_err(|_| anyhow!("invalid seed `{}`", seed))?,
+        None => DEFAULT_SEED,
+    };
+    let size = match option_value(args, "--size")? {
+        Some(size) => size.parse::<usize>().map_err(|_| anyhow!("invalid size `{}`", size))?,
+        None => DEFAULT_SIZE,
+    };
+
+    generators::generate(day, seed, size).ok_or_else(|| anyhow!("no generator for day {}", day))
+}
+
+fn day_argument(args: &[String]) -> anyhow::Result<Option<DayNum>> {
+    match args.first().map(String::as_str) {
+        Some("all") => Ok(None),
+        Some(day) => Ok(Some(parse_day(day)?)),
+        None => bail!("missing day\n{}", USAGE),
+    }
+}
+
+fn part_option(args: &[String]) -> anyhow::Result<Option<Part>> {
+    Ok(option_value(args, "--part")?
+        .map(|part| part.parse::<Part>())
+        .transpose()?)
+}
+
+fn input_option(args: &[String]) -> anyhow::Result<Option<InputSource>> {
+    Ok(option_value(args, "--input")?
+        .map(|source| source.parse::<InputSource>())
+        .transpose()?)
+}
+
+fn parse_day(s: &str) -> anyhow::Result<DayNum> {
+    s.parse::<DayNum>()
+        .map_err(|_| anyhow!("invalid day `{}`", s))
+}
+
+fn option_value<'a>(args: &'a [String], name: &str) -> anyhow::Result<Option<&'a str>> {
+    match args.iter().position(|arg| arg == name) {
+        None => Ok(None),
+        Some(index) => args
+            .get(index + 1)
+            .map(|value| Some(value.as_str()))
+            .ok_or_else(|| anyhow!("missing value for `{}`", name)),
+    }
+}
@@ -1,6 +0,0 @@
-use advent_of_code::ex1::Day1;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day1::timeit_solve_default_file();
-}
@@ -1,7 +0,0 @@
-use advent_of_code::ex10::Day10;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day10::timeit_solve_default_file();
-    Day10::timeit_solution();
-}
@@ -1,7 +0,0 @@
-use advent_of_code::ex12::Day12;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day12::timeit_solve_default_file();
-    Day12::timeit_solution();
-}
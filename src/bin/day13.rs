@@ -1,8 +0,0 @@
-use advent_of_code::ex13::{Day13VariantA};
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day13VariantA::timeit_solve_default_file();
-    // DayXXVariantB::timeit_solution();
-    // DayXXVariantB::timeit_solution_with_label("variant C");
-}
\ No newline at end of file
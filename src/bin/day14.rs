@@ -1,9 +0,0 @@
-use advent_of_code::ex14::Day14VariantA;
-use advent_of_code::ex14b::Day14VariantB;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day14VariantA::timeit_solve_default_file();
-    Day14VariantA::timeit_solution();
-    Day14VariantB::timeit_solution_with_label("variant B");
-}
@@ -1,10 +0,0 @@
-use advent_of_code::ex15::Day15VersionA;
-use advent_of_code::ex15b::Day15VersionB;
-use advent_of_code::ex15c::Day15VersionC;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day15VersionA::timeit_solve_default_file();
-    Day15VersionB::timeit_solve_default_file();
-    Day15VersionC::timeit_solve_default_file();
-}
@@ -1,7 +0,0 @@
-use advent_of_code::ex16::Day16;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day16::timeit_solve_default_file();
-    //Day16::timeit_solution();
-}
\ No newline at end of file
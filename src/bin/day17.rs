@@ -1,10 +0,0 @@
-use advent_of_code::ex17::Day17;
-use advent_of_code::ex17other::Day17other;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day17::timeit_solve_default_file();
-    // Day17::timeit_solution();
-    Day17other::timeit_solve_default_file();
-    // Day17::timeit_solution();
-} 
\ No newline at end of file
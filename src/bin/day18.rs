@@ -1,7 +0,0 @@
-use advent_of_code::ex18::Day18;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day18::timeit_solve_default_file();
-    //Day18::timeit_solution();
-}
\ No newline at end of file
@@ -1,8 +0,0 @@
-//use advent_of_code::ex19::Day19;
-use advent_of_code::ex19petgraph::Day19petgraph;
-use aoc_utils::DaySolver;
-
-fn main() {
-    //Day19::timeit_solve_default_file();
-    Day19petgraph::timeit_solve_default_file();
-}
\ No newline at end of file
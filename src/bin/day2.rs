@@ -1,6 +0,0 @@
-use advent_of_code::ex2::Day2;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day2::timeit_solve_default_file();
-}
@@ -1,9 +0,0 @@
-use advent_of_code::ex20::Day20;
-use advent_of_code::ex20b::Day20b;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day20::timeit_solve_default_file();
-    Day20b::timeit_solve_default_file();
-    //Day20::timeit_solution();
-}
\ No newline at end of file
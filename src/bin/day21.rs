@@ -1,6 +0,0 @@
-use advent_of_code::ex21::Day21;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day21::timeit_solve_default_file();
-}
\ No newline at end of file
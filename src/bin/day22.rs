@@ -1,7 +0,0 @@
-use advent_of_code::ex22::Day22;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day22::timeit_solve_default_file();
-    //Day22::timeit_solution();
-}
\ No newline at end of file
@@ -1,9 +0,0 @@
-use advent_of_code::ex23::Day23;
-use advent_of_code::ex23b::Day23b;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day23::timeit_solve_default_file();
-    Day23b::timeit_solve_default_file();
-    //Day23::timeit_solution();
-}
\ No newline at end of file
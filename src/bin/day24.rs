@@ -1,9 +0,0 @@
-use advent_of_code::ex24::Day24;
-use advent_of_code::ex24pdo::Day24b;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day24b::timeit_solve_default_file();
-    Day24::timeit_solve_default_file();
-    //Day24::timeit_solution();
-}
\ No newline at end of file
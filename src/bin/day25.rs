@@ -1,8 +0,0 @@
-use advent_of_code::ex25::Day25;
-use advent_of_code::ex25b::Day25b;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day25::timeit_solve_default_file();
-    Day25b::timeit_solve_default_file();
-}
@@ -1,6 +0,0 @@
-use advent_of_code::ex3::Day3;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day3::timeit_solve_default_file();
-}
@@ -1,6 +0,0 @@
-use advent_of_code::ex4::Day4;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day4::timeit_solve_default_file();
-}
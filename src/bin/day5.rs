@@ -1,6 +0,0 @@
-use advent_of_code::ex5::Day5;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day5::timeit_solve_default_file();
-}
@@ -1,6 +0,0 @@
-use advent_of_code::ex6::Day6;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day6::timeit_solve_default_file();
-}
@@ -1,6 +0,0 @@
-use advent_of_code::ex7::Day7;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day7::timeit_solve_default_file();
-}
@@ -1,6 +0,0 @@
-use advent_of_code::ex8::Day8;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day8::timeit_solve_default_file();
-}
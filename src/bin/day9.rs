@@ -1,6 +0,0 @@
-use advent_of_code::ex9::Day9;
-use aoc_utils::DaySolver;
-
-fn main() {
-    Day9::timeit_solve_default_file();
-}
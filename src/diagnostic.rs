@@ -0,0 +1,180 @@
+use std::fmt;
+use std::ops::Range;
+
+// -----------------------------------------------------------------------------
+
+/// Parse error pinned to a line of the input, displayed as the offending line with the
+/// `columns` underlined:
+///
+/// ```text
+/// unknown operation `abc`
+///  --> data_files/ex8.txt:3:1
+///   |
+/// 3 | abc +1
+///   | ^^^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub file:    Option<String>,
+    pub line:    usize,
+    pub columns: Range<usize>,
+    pub text:    String,
+    pub message: String,
+}
+
+/// Implemented by the per-day errors, so they can be turned into a `Diagnostic`.
+pub trait Locate {
+    /// Line of the input starting from 1, `None` when the error is not tied to a line.
+    fn line(&self) -> Option<usize>;
+
+    /// Offending part of the line, the whole line is underlined when it is `None`.
+    fn fragment(&self) -> Option<String> {
+        None
+    }
+}
+
+impl Diagnostic {
+    pub fn new(input: &str, line: usize, fragment: Option<&str>, message: impl ToString) -> Self {
+        let text = input
+            .lines()
+            .nth(line.saturating_sub(1))
+            .unwrap_or_default();
+
+        Diagnostic {
+            file:    None,
+            line,
+            columns: span(text, fragment),
+            text:    text.to_owned(),
+            message: message.to_string(),
+        }
+    }
+
+    pub fn from_error<E>(input: &str, err: &E) -> Option<Self>
+    where
+        E: Locate + fmt::Display,
+    {
+        let line = err.line()?;
+        Some(Diagnostic::new(input, line, err.fragment().as_deref(), err))
+    }
+
+    /// First underlined column, counted in characters from 1.
+    pub fn column(&self) -> usize {
+        self.text[..self.columns.start].chars().count() + 1
+    }
+}
+
+/// Byte range of `fragment` in `text`, whole trimmed line when it is not there.
+fn span(text: &str, fragment: Option<&str>) -> Range<usize> {
+    let found = fragment
+        .filter(|fragment| !fragment.is_empty())
+        .and_then(|fragment| Some((text.find(fragment)?, fragment.len())));
+
+    match found {
+        Some((start, len)) => start..start + len,
+        None => {
+            let start = text.len() - text.trim_start().len();
+            start..text.trim_end().len().max(start)
+        },
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        let underline = self.text[self.columns.clone()].chars().count().max(1);
+
+        writeln!(f, "{}", self.message)?;
+        writeln!(
+            f,
+            "{}--> {}:{}:{}",
+            gutter,
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column()
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(self.column() - 1),
+            "^".repeat(underline)
+        )
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+pub trait Diagnose<T> {
+    /// Attaches a `Diagnostic` to errors which know their line, the original error can
+    /// still be downcasted.
+    fn diagnose(self, input: &str) -> anyhow::Result<T>;
+
+    /// Same as `diagnose` for errors which do not know their line themselves.
+    fn diagnose_line(self, input: &str, line: usize) -> anyhow::Result<T>;
+}
+
+impl<T, E> Diagnose<T> for Result<T, E>
+where
+    E: Locate + std::error::Error + Send + Sync + 'static,
+{
+    fn diagnose(self, input: &str) -> anyhow::Result<T> {
+        self.map_err(|err| match Diagnostic::from_error(input, &err) {
+            Some(diagnostic) => anyhow::Error::new(err).context(diagnostic),
+            None => anyhow::Error::new(err),
+        })
+    }
+
+    fn diagnose_line(self, input: &str, line: usize) -> anyhow::Result<T> {
+        self.map_err(|err| {
+            let diagnostic = Diagnostic::new(input, line, err.fragment().as_deref(), &err);
+            anyhow::Error::new(err).context(diagnostic)
+        })
+    }
+}
+
+/// Names the input in the diagnostic carried by `err`, if there is one.
+pub fn with_file(mut err: anyhow::Error, file: &str) -> anyhow::Error {
+    if let Some(diagnostic) = err.downcast_mut::<Diagnostic>() {
+        diagnostic.file = Some(file.to_owned());
+    }
+
+    err
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("nop +0\nabc +1", 2, Some("abc") => (0..3, 1); "fragment")]
+    #[test_case("nop +0\nacc +x", 2, Some("+x") => (4..6, 5); "fragment inside")]
+    #[test_case("  acc x  ", 1, None => (2..7, 3); "whole trimmed line")]
+    #[test_case("acc +1", 1, Some("jmp") => (0..6, 1); "fragment not found")]
+    #[test_case("żółw +1", 1, Some("+1") => (8..10, 6); "multibyte prefix")]
+    fn diagnostic_span(input: &str, line: usize, fragment: Option<&str>) -> (Range<usize>, usize) {
+        let diagnostic = Diagnostic::new(input, line, fragment, "error");
+        (diagnostic.columns.clone(), diagnostic.column())
+    }
+
+    #[test]
+    fn diagnostic_display() {
+        let mut diagnostic = Diagnostic::new("nop +0\nabc +1", 2, Some("abc"), "unknown `abc`");
+        diagnostic.file = Some("data_files/ex8.txt".to_owned());
+
+        assert_eq!(
+            diagnostic.to_string(),
+            "unknown `abc`\n --> data_files/ex8.txt:2:1\n  |\n2 | abc +1\n  | ^^^"
+        );
+    }
+
+    #[test]
+    fn diagnostic_line_out_of_input() {
+        let diagnostic = Diagnostic::new("nop +0", 3, None, "error");
+
+        assert_eq!(diagnostic.text, "");
+        assert_eq!(diagnostic.columns, 0..0);
+        assert!(diagnostic.to_string().ends_with("  | ^"));
+    }
+}
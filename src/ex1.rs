@@ -1,47 +1,186 @@
+use thiserror::Error;
+
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 
+use super::diagnostic::{Diagnose, Locate};
+use super::part::PartTwo;
+
+pub const TARGET: i32 = 2020;
+
 pub struct Day1;
 
 impl DaySolver for Day1 {
-    type Output = i32;
+    type Output = i64;
 
     const INFO: DayInfo = DayInfo::with_day_and_file("day_1", "data_files/ex1.txt");
 
     fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
-        let nums = _s.lines().map(|line| line.parse::<i32>().unwrap()).collect();
-        let result_vec = check_sum(nums, 2020);
-        Ok(result_vec[0]) // return first as it is in task but checks for all pairs
+        let nums = parse_numbers(_s).diagnose(_s)?;
+        let res = first_product(&nums, 2, TARGET)?;
+        Ok(res)
     }
 }
 
-fn check_sum(nums: Vec<i32>, sum: i32) -> Vec<i32> {
-    let v: Vec<i32> = {
-        let mut v = nums;
-        v.sort_unstable();
-        v
-    };
+impl PartTwo for Day1 {
+    type OutputPartTwo = i64;
 
-    let mut res: Vec<i32> = vec![];
-    let mut right_border: usize = v.len() - 1;
-    let mut index: usize = 0;
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+        let nums = parse_numbers(s).diagnose(s)?;
+        let res = first_product(&nums, 3, TARGET)?;
+        Ok(res)
+    }
+}
 
-    while index != right_border {
-        let value1 = v[index];
-        let value2 = v[right_border];
-        let checked_sum = value1 + value2;
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex1Error {
+    #[error("line {line}: invalid number `{text}`")]
+    InvalidNumber { line: usize, text: String },
 
-        if checked_sum <= sum {
-            if checked_sum == sum {
-                res.push(value1 * value2);
-            }
-            index += 1;
-        } else {
-            right_border -= 1;
+    #[error("no {entries} entries sum to {target}")]
+    NoMatchingEntries { entries: usize, target: i32 },
+
+    #[error("product of entries {0:?} does not fit in i64")]
+    ProductOverflow(Vec<i32>),
+}
+
+impl Locate for Ex1Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex1Error::InvalidNumber { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex1Error::InvalidNumber { text, .. } => Some(text.to_owned()),
+            _ => None,
         }
     }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Matches {
+    First,
+    All,
+}
+
+fn parse_numbers(input: &str) -> Result<Vec<i32>, Ex1Error> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(line_num, line)| {
+            line.trim()
+                .parse::<i32>()
+                .map_err(|_| Ex1Error::InvalidNumber {
+                    line: line_num + 1,
+                    text: line.to_owned(),
+                })
+        })
+        .collect()
+}
+
+/// Combinations of `k` entries summing to `target`, each one sorted and listed once even
+/// when the report repeats values.
+pub fn k_sum(nums: &[i32], k: usize, target: i32, matches: Matches) -> Vec<Vec<i32>> {
+    let mut sorted = nums.to_vec();
+    sorted.sort_unstable();
+
+    let mut found = Vec::new();
+
+    if k > 0 {
+        k_sum_sorted(&sorted, k, i64::from(target), matches, &mut Vec::new(), &mut found);
+    }
+
+    found
+}
+
+fn k_sum_sorted(
+    sorted: &[i32],
+    k: usize,
+    target: i64,
+    matches: Matches,
+    chosen: &mut Vec<i32>,
+    found: &mut Vec<Vec<i32>>,
+) {
+    let done = |found: &Vec<Vec<i32>>| matches == Matches::First && !found.is_empty();
+
+    match k {
+        1 => {
+            if sorted.binary_search_by(|num| i64::from(*num).cmp(&target)).is_ok() {
+                let mut combination = chosen.clone();
+                combination.push(target as i32);
+                found.push(combination);
+            }
+        },
+        2 => {
+            if sorted.len() < 2 {
+                return;
+            }
+
+            let mut left: usize = 0;
+            let mut right: usize = sorted.len() - 1;
+
+            while left < right && !done(found) {
+                let checked_sum = i64::from(sorted[left]) + i64::from(sorted[right]);
+
+                if checked_sum < target {
+                    left += 1;
+                } else if checked_sum > target {
+                    right -= 1;
+                } else {
+                    let mut combination = chosen.clone();
+                    combination.extend([sorted[left], sorted[right]]);
+                    found.push(combination);
+
+                    while left < right && sorted[left] == sorted[left + 1] {
+                        left += 1;
+                    }
+                    left += 1;
+                }
+            }
+        },
+        _ => {
+            for (index, num) in sorted.iter().enumerate() {
+                if done(found) {
+                    break;
+                }
+
+                if index > 0 && sorted[index - 1] == *num {
+                    continue;
+                }
+
+                chosen.push(*num);
+                k_sum_sorted(
+                    &sorted[index + 1..],
+                    k - 1,
+                    target - i64::from(*num),
+                    matches,
+                    chosen,
+                    found,
+                );
+                chosen.pop();
+            }
+        },
+    }
+}
+
+pub fn product(entries: &[i32]) -> Result<i64, Ex1Error> {
+    entries
+        .iter()
+        .try_fold(1i64, |product, entry| product.checked_mul(i64::from(*entry)))
+        .ok_or_else(|| Ex1Error::ProductOverflow(entries.to_vec()))
+}
 
-    res
+/// Product of the first `k` entries summing to `target`, as asked by the expense report.
+pub fn first_product(nums: &[i32], k: usize, target: i32) -> Result<i64, Ex1Error> {
+    let found = k_sum(nums, k, target, Matches::First);
+    let combination = found
+        .first()
+        .ok_or(Ex1Error::NoMatchingEntries { entries: k, target })?;
+
+    product(combination)
 }
 
 #[cfg(test)]
@@ -52,10 +191,65 @@ mod tests {
     #[test_case(vec![1721, 979, 366, 299, 675, 1456], 2020 => vec![514579]; "test from website")]
     #[test_case(vec![2040, -20, 366, 299, 675, 1456], 2020 => vec![-40800]; "negative value in array")]
     #[test_case(vec![-2040, 20, 366, 299, 675, 1456], -2020 => vec![-40800]; "negative sum result")]
-    #[test_case(vec![1721, 979, 366, 0, 675, 1456], 2020 => Vec::<i32>::new(); "lack of matched pair")]
+    #[test_case(vec![1721, 979, 366, 0, 675, 1456], 2020 => Vec::<i64>::new(); "lack of matched pair")]
     #[test_case(vec![2, 2018, 20, 2000], 2020 => vec![4036, 40000]; "2 matching pairs")]
     #[test_case(vec![2, 2018, 20, 2000, 40], 2020 => vec![4036, 40000]; "2 matching pairs one exstra")]
-    fn test_check_sum(nums: Vec<i32>, sum: i32) -> Vec<i32> {
-        check_sum(nums, sum)
+    #[test_case(vec![], 2020 => Vec::<i64>::new(); "empty input")]
+    fn test_check_sum(nums: Vec<i32>, sum: i32) -> Vec<i64> {
+        k_sum(&nums, 2, sum, Matches::All)
+            .iter()
+            .map(|combination| product(combination).unwrap())
+            .collect()
+    }
+
+    #[test_case("1721\n979" => Ok(vec![1721, 979]); "valid")]
+    #[test_case("1721\n97a" => Err(Ex1Error::InvalidNumber{line: 2, text: "97a".to_owned()}); "invalid number")]
+    fn ex1_parse_numbers(s: &str) -> Result<Vec<i32>, Ex1Error> {
+        parse_numbers(s)
+    }
+
+    #[test_case(&[1721, 979, 366, 299, 675, 1456], 3, 2020, Matches::All => vec![vec![366, 675, 979]]; "triple from website")]
+    #[test_case(&[1, 2, 3, 4, 5], 3, 9, Matches::All => vec![vec![1, 3, 5], vec![2, 3, 4]]; "all triples")]
+    #[test_case(&[1, 2, 3, 4, 5], 3, 9, Matches::First => vec![vec![1, 3, 5]]; "first triple")]
+    #[test_case(&[1, 1, 1, 2, 2], 2, 3, Matches::All => vec![vec![1, 2]]; "repeated values")]
+    #[test_case(&[1, 1, 2], 2, 2, Matches::All => vec![vec![1, 1]]; "same value twice")]
+    #[test_case(&[1, 2, 3, 4, 5], 4, 14, Matches::All => vec![vec![2, 3, 4, 5]]; "four entries")]
+    #[test_case(&[1, 2, 3], 1, 2, Matches::All => vec![vec![2]]; "single entry")]
+    #[test_case(&[1, 2, 3], 4, 6, Matches::All => Vec::<Vec<i32>>::new(); "more entries than report")]
+    #[test_case(&[1, 2, 3], 0, 0, Matches::All => Vec::<Vec<i32>>::new(); "no entries")]
+    fn ex1_k_sum(nums: &[i32], k: usize, target: i32, matches: Matches) -> Vec<Vec<i32>> {
+        k_sum(nums, k, target, matches)
+    }
+
+    #[test]
+    fn ex1_solution_part_two() {
+        let input = "1721\n979\n366\n299\n675\n1456";
+        assert_eq!(Day1::solution_part_two(input).unwrap(), 241861950);
+    }
+
+    #[test_case(&[2020] => Ok(2020); "single entry")]
+    #[test_case(&[] => Ok(1); "no entries")]
+    #[test_case(&[i32::MIN, i32::MIN] => Ok(1 << 62); "largest product")]
+    #[test_case(&[i32::MIN, i32::MIN, 2] => Err(Ex1Error::ProductOverflow(vec![i32::MIN, i32::MIN, 2])); "overflow")]
+    fn ex1_product(entries: &[i32]) -> Result<i64, Ex1Error> {
+        product(entries)
+    }
+
+    #[test]
+    fn ex1_product_overflow() {
+        let err = Day1::solution_part_two("2000000000\n-2000000000\n2020").unwrap_err();
+        assert_eq!(
+            err.downcast::<Ex1Error>().unwrap(),
+            Ex1Error::ProductOverflow(vec![-2000000000, 2020, 2000000000])
+        );
+    }
+
+    #[test]
+    fn ex1_no_matching_pair() {
+        let err = Day1::solution("1\n2").unwrap_err();
+        assert_eq!(
+            err.downcast::<Ex1Error>().unwrap(),
+            Ex1Error::NoMatchingEntries { entries: 2, target: 2020 }
+        );
     }
 }
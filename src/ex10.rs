@@ -1,12 +1,12 @@
-use std::fs;
-use std::io::{self};
 use std::ops::ControlFlow::{Break, Continue};
-use std::path::Path;
 use thiserror::Error;
 
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 
+use super::diagnostic::{Diagnose, Locate};
+use super::part::PartTwo;
+
 pub struct Day10;
 
 impl DaySolver for Day10 {
@@ -19,28 +19,65 @@ impl DaySolver for Day10 {
     }
 }
 
-fn get_data<P>(path: P) -> io::Result<String>
-where
-    P: AsRef<Path>,
-{
-    let contents = fs::read_to_string(path)?;
-    Ok(contents)
+impl PartTwo for Day10 {
+    type OutputPartTwo = u128;
+
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+        let adapters = parse_adapters(s).diagnose(s)?;
+        Ok(count_arrangements(&adapters)?)
+    }
 }
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum AdaptersConnectError {
     #[error("To big difference between adaper `{0}` and `{1}`")]
     ToBigDifference(u16, u16),
+
+    #[error("line {line}: invalid adapter joltage `{text}`")]
+    InvalidJoltage { line: usize, text: String },
+
+    #[error("Arrangements reaching adapter `{0}` do not fit in u128")]
+    ArrangementsOverflow(u16),
 }
 
-pub fn connect_adapters(adapters: &str) -> anyhow::Result<u64> {
+impl Locate for AdaptersConnectError {
+    fn line(&self) -> Option<usize> {
+        match self {
+            AdaptersConnectError::InvalidJoltage { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            AdaptersConnectError::InvalidJoltage { text, .. } => Some(text.to_owned()),
+            _ => None,
+        }
+    }
+}
+
+/// Joltages of the adapters in ascending order.
+pub fn parse_adapters(adapters: &str) -> Result<Vec<u16>, AdaptersConnectError> {
     let mut res: Vec<u16> = adapters
         .lines()
-        .map(|f| f.parse::<u16>().unwrap())
-        .collect();
+        .enumerate()
+        .map(|(line_num, line)| {
+            line.parse::<u16>()
+                .map_err(|_| AdaptersConnectError::InvalidJoltage {
+                    line: line_num + 1,
+                    text: line.to_owned(),
+                })
+        })
+        .collect::<Result<Vec<u16>, AdaptersConnectError>>()?;
 
     res.sort_unstable();
 
+    Ok(res)
+}
+
+pub fn connect_adapters(adapters: &str) -> anyhow::Result<u64> {
+    let res = parse_adapters(adapters).diagnose(adapters)?;
+
     let connected =
         res.iter().try_fold(
             (0u16, 0u16, 0u16),
@@ -54,39 +91,135 @@ pub fn connect_adapters(adapters: &str) -> anyhow::Result<u64> {
 
     match connected {
         Continue(ok) => Ok((ok.0 * (ok.1 + 1)).into()),
-        Break(err) => Err(anyhow::anyhow!("{}", err)),
+        Break(err) => Err(err.into()),
+    }
+}
+
+/// Number of ways to connect the outlet to the device through sorted `adapters`, each adapter
+/// counts the ways of reaching the (at most three) adapters it can be plugged into.
+pub fn count_arrangements(adapters: &[u16]) -> Result<u128, AdaptersConnectError> {
+    let joltages: Vec<u16> = std::iter::once(0).chain(adapters.iter().copied()).collect();
+    let mut ways: Vec<u128> = vec![0; joltages.len()];
+    ways[0] = 1;
+
+    for index in 1..joltages.len() {
+        if joltages[index] - joltages[index - 1] > 3 {
+            return Err(AdaptersConnectError::ToBigDifference(
+                joltages[index - 1],
+                joltages[index],
+            ));
+        }
+
+        ways[index] = (index.saturating_sub(3)..index)
+            .filter(|previous| joltages[index] - joltages[*previous] <= 3)
+            .try_fold(0u128, |sum, previous| sum.checked_add(ways[previous]))
+            .ok_or(AdaptersConnectError::ArrangementsOverflow(joltages[index]))?;
     }
+
+    Ok(ways[joltages.len() - 1])
+}
+
+/// At most `limit` concrete arrangements of sorted `adapters`, useful for debugging, as the
+/// number of all of them grows exponentially.
+pub fn arrangements(adapters: &[u16], limit: usize) -> Result<Vec<Vec<u16>>, AdaptersConnectError> {
+    count_arrangements(adapters)?;
+
+    let mut res: Vec<Vec<u16>> = Vec::new();
+    collect_arrangements(adapters, 0, 0, &mut Vec::new(), &mut res, limit);
+
+    Ok(res)
 }
 
-pub fn run<P>(path: P) -> anyhow::Result<()>
-where
-    P: AsRef<Path>,
-{
-    let data = get_data(path)?;
-    let res = connect_adapters(data.as_str())?;
-    println!("Result of multiplication is: {}", res);
-    Ok(())
+fn collect_arrangements(
+    adapters: &[u16],
+    joltage: u16,
+    start: usize,
+    chain: &mut Vec<u16>,
+    res: &mut Vec<Vec<u16>>,
+    limit: usize,
+) {
+    if start == adapters.len() {
+        res.push(chain.to_owned());
+        return;
+    }
+
+    for (index, next) in adapters.iter().enumerate().skip(start) {
+        if *next - joltage > 3 || res.len() >= limit {
+            break;
+        }
+
+        chain.push(*next);
+        collect_arrangements(adapters, *next, index + 1, chain, res, limit);
+        chain.pop();
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    //use test_case::test_case;
+    use crate::input::{self, InputSource};
+    use test_case::test_case;
 
-    // #[test_case("data_files/ex10.txt" => Ok(220))]
-    // #[test_case("data_files/ex10_error.txt" => Err(AdaptersConnectError::ToBigDifference(49, 200)))]
-    // fn test_extract_seat_num(s: &str) -> Result<u64, AdaptersConnectError> {
-    //     let data = get_data(s).unwrap();
-    //     connect_adapters(data.as_str())
-    // }
+    #[test_case("data_files/ex10.txt" => Ok(220))]
+    #[test_case("data_files/ex10_error.txt" => Err(AdaptersConnectError::ToBigDifference(49, 200)))]
+    fn test_ex10_from_file(s: &str) -> Result<u64, AdaptersConnectError> {
+        input::solve::<Day10>(&InputSource::from(s)).map_err(|err| err.downcast().unwrap())
+    }
 
     #[test]
-    fn test_ex10_run_no_file() {
-        assert!(run("aaa").is_err())
+    fn test_ex10_invalid_joltage() {
+        let err = connect_adapters("1\n4\nx").unwrap_err();
+
+        assert_eq!(
+            err.downcast::<AdaptersConnectError>().unwrap(),
+            AdaptersConnectError::InvalidJoltage {
+                line: 3,
+                text: "x".to_owned(),
+            }
+        );
     }
 
     #[test]
-    fn test_ex10_run_file_exists() {
-        assert!(!run("data_files/ex10.txt").is_err())
+    fn test_ex10_no_file() {
+        assert!(input::solve::<Day10>(&InputSource::from("aaa")).is_err())
+    }
+
+    #[test_case("data_files/ex10.txt" => Ok(19208))]
+    #[test_case("data_files/ex10_error.txt" => Err(AdaptersConnectError::ToBigDifference(49, 200)))]
+    fn test_ex10_part_two_from_file(s: &str) -> Result<u128, AdaptersConnectError> {
+        let input = InputSource::from(s).read().unwrap();
+        Day10::solution_part_two(&input).map_err(|err| err.downcast().unwrap())
+    }
+
+    #[test_case(&[1, 4, 5, 6, 7, 10, 11, 12, 15, 16, 19] => Ok(8); "short example")]
+    #[test_case(&[] => Ok(1); "no adapters")]
+    #[test_case(&[1, 2, 3, 4] => Ok(7); "every adapter optional but last")]
+    #[test_case(&[4] => Err(AdaptersConnectError::ToBigDifference(0, 4)); "outlet too far")]
+    fn test_ex10_count_arrangements(adapters: &[u16]) -> Result<u128, AdaptersConnectError> {
+        count_arrangements(adapters)
+    }
+
+    #[test]
+    fn test_ex10_arrangements_overflow() {
+        let adapters: Vec<u16> = (1..=200).collect();
+
+        assert_eq!(
+            count_arrangements(&adapters),
+            Err(AdaptersConnectError::ArrangementsOverflow(147))
+        );
+    }
+
+    #[test]
+    fn test_ex10_arrangements() {
+        let adapters = [1, 4, 5, 6, 7, 10, 11, 12, 15, 16, 19];
+
+        assert_eq!(arrangements(&adapters, 100).unwrap().len(), 8);
+        assert_eq!(
+            arrangements(&adapters, 2).unwrap(),
+            vec![
+                vec![1, 4, 5, 6, 7, 10, 11, 12, 15, 16, 19],
+                vec![1, 4, 5, 6, 7, 10, 12, 15, 16, 19],
+            ]
+        );
     }
 }
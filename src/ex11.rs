@@ -1,13 +1,51 @@
-use std::fs;
-use std::io::{self};
-use std::path::Path;
+use thiserror::Error;
 
-pub fn get_data<P>(path: P) -> io::Result<String>
-where
-    P: AsRef<Path>,
-{
-    let contents = fs::read_to_string(path)?;
-    Ok(contents)
+use aoc_utils::DayInfo;
+use aoc_utils::DaySolver;
+
+use super::diagnostic::{Diagnose, Locate};
+use super::part::PartTwo;
+
+pub struct Day11VariantA;
+
+impl DaySolver for Day11VariantA {
+    type Output = usize;
+
+    const INFO: DayInfo =
+        DayInfo::with_day_and_file_and_variant("day_11", "data_files/ex11.txt", "base");
+
+    fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
+        count_occupied_seats(_s, &SeatingRules::PART_ONE).diagnose(_s)
+    }
+}
+
+impl PartTwo for Day11VariantA {
+    type OutputPartTwo = usize;
+
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+        count_occupied_seats(s, &SeatingRules::PART_TWO).diagnose(s)
+    }
+}
+
+pub struct Day11VariantB;
+
+impl DaySolver for Day11VariantB {
+    type Output = usize;
+
+    const INFO: DayInfo =
+        DayInfo::with_day_and_file_and_variant("day_11", "data_files/ex11.txt", "new board");
+
+    fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
+        count_occupied_seats2(_s, &SeatingRules::PART_ONE).diagnose(_s)
+    }
+}
+
+impl PartTwo for Day11VariantB {
+    type OutputPartTwo = usize;
+
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+        count_occupied_seats2(s, &SeatingRules::PART_TWO).diagnose(s)
+    }
 }
 
 #[derive(PartialEq, Clone, Debug)]
@@ -17,28 +55,176 @@ enum LocationState {
     Gap,
 }
 
-fn num_occupied_adjacent_cells(board: &[Vec<LocationState>], row: &i32, col: &i32) -> u8 {
-    (1..=8)
-        .map(|field| match field {
-            1 => (-1, -1),
-            2 => (0, -1),
-            3 => (1, -1),
-            4 => (-1, 0),
-            5 => (1, 0),
-            6 => (-1, 1),
-            7 => (0, 1),
-            8 => (1, 1),
-            _ => unreachable!(),
-        })
-        .filter(|(r, c)| {
-            (0..10).contains(&(*r + row))
-                && (0..10).contains(&(*c + col))
-                && board[(row + r) as usize][(col + c) as usize] == LocationState::Occupied
+/// Which seats are looked at when deciding whether a seat changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// The eight surrounding locations.
+    Adjacent,
+    /// The first seat seen in each of the eight directions, floor is skipped.
+    Visible,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SeatingRules {
+    pub neighbourhood: Neighbourhood,
+    /// Occupied seat is left once at least this many of its neighbours are occupied.
+    pub tolerance:     u8,
+}
+
+impl SeatingRules {
+    pub const PART_ONE: SeatingRules = SeatingRules {
+        neighbourhood: Neighbourhood::Adjacent,
+        tolerance:     4,
+    };
+
+    pub const PART_TWO: SeatingRules = SeatingRules {
+        neighbourhood: Neighbourhood::Visible,
+        tolerance:     5,
+    };
+}
+
+const DIRECTIONS: [(i32, i32); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Seating that still changes after this many rounds is reported instead of followed forever.
+pub const MAX_ROUNDS: usize = 10_000;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex11Error {
+    #[error("line {line}: unknown location `{location}` in `{text}`")]
+    UnknownLocation {
+        line:     usize,
+        location: char,
+        text:     String,
+    },
+
+    #[error("line {line}: expected {expected} locations, found {found} in `{text}`")]
+    RaggedRow {
+        line:     usize,
+        expected: usize,
+        found:    usize,
+        text:     String,
+    },
+
+    #[error("empty board")]
+    EmptyBoard,
+
+    #[error("seating still changes after {0} rounds")]
+    NotStable(usize),
+}
+
+impl Locate for Ex11Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex11Error::UnknownLocation { line, .. } | Ex11Error::RaggedRow { line, .. } => {
+                Some(*line)
+            },
+            Ex11Error::EmptyBoard | Ex11Error::NotStable(_) => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex11Error::UnknownLocation { location, .. } => Some(location.to_string()),
+            _ => None,
+        }
+    }
+}
+
+fn parse_board(data: &str) -> Result<Vec<Vec<LocationState>>, Ex11Error> {
+    use LocationState::*;
+
+    let mut board: Vec<Vec<LocationState>> = Vec::new();
+
+    for (line_num, line) in data.lines().enumerate() {
+        let row = line
+            .chars()
+            .map(|f| match f {
+                'L' => Ok(Empty),
+                '#' => Ok(Occupied),
+                '.' => Ok(Gap),
+                location => Err(Ex11Error::UnknownLocation {
+                    line: line_num + 1,
+                    location,
+                    text: line.to_owned(),
+                }),
+            })
+            .collect::<Result<Vec<LocationState>, Ex11Error>>()?;
+
+        if let Some(first_row) = board.first() {
+            if first_row.len() != row.len() {
+                return Err(Ex11Error::RaggedRow {
+                    line:     line_num + 1,
+                    expected: first_row.len(),
+                    found:    row.len(),
+                    text:     line.to_owned(),
+                });
+            }
+        }
+
+        board.push(row);
+    }
+
+    if board.is_empty() || board[0].is_empty() {
+        return Err(Ex11Error::EmptyBoard);
+    }
+
+    Ok(board)
+}
+
+fn location(board: &[Vec<LocationState>], row: i32, col: i32) -> Option<&LocationState> {
+    board
+        .get(usize::try_from(row).ok()?)?
+        .get(usize::try_from(col).ok()?)
+}
+
+fn neighbour(
+    board: &[Vec<LocationState>],
+    (row, col): (usize, usize),
+    (r, c): (i32, i32),
+    neighbourhood: Neighbourhood,
+) -> Option<&LocationState> {
+    let (mut row, mut col) = (row as i32 + r, col as i32 + c);
+
+    loop {
+        let location = location(board, row, col)?;
+
+        if neighbourhood == Neighbourhood::Adjacent || *location != LocationState::Gap {
+            return Some(location);
+        }
+
+        row += r;
+        col += c;
+    }
+}
+
+fn num_occupied_neighbours(
+    board: &[Vec<LocationState>],
+    row: usize,
+    col: usize,
+    neighbourhood: Neighbourhood,
+) -> u8 {
+    DIRECTIONS
+        .iter()
+        .filter(|direction| {
+            neighbour(board, (row, col), **direction, neighbourhood)
+                == Some(&LocationState::Occupied)
         })
         .count() as u8
 }
 
-fn update_board2(base_board: &[Vec<LocationState>]) -> (bool, usize, Vec<Vec<LocationState>>) {
+fn update_board2(
+    base_board: &[Vec<LocationState>],
+    rules: &SeatingRules,
+) -> (bool, usize, Vec<Vec<LocationState>>) {
     use LocationState::*;
 
     let mut occupied_seats_num: usize = 0;
@@ -50,14 +236,14 @@ fn update_board2(base_board: &[Vec<LocationState>]) -> (bool, usize, Vec<Vec<Loc
 
     for row in 0..=last_row {
         for col in 0..=last_col {
-            let occupied = num_occupied_adjacent_cells(base_board, &(row as i32), &(col as i32));
+            let occupied = num_occupied_neighbours(base_board, row, col, rules.neighbourhood);
             match base_board[row][col] {
                 Empty if occupied == 0 => {
                     result_board[row][col] = Occupied;
                     was_any_seat_changed = true;
                     occupied_seats_num += 1;
                 },
-                Occupied if occupied >= 4 => {
+                Occupied if occupied >= rules.tolerance => {
                     result_board[row][col] = Empty;
                     was_any_seat_changed = true;
                 },
@@ -72,37 +258,26 @@ fn update_board2(base_board: &[Vec<LocationState>]) -> (bool, usize, Vec<Vec<Loc
     (was_any_seat_changed, occupied_seats_num, result_board)
 }
 
-pub fn count_occupied_seats2(data: &str) -> usize {
-    use LocationState::*;
+pub fn count_occupied_seats2(data: &str, rules: &SeatingRules) -> Result<usize, Ex11Error> {
+    let mut board1 = parse_board(data)?;
 
-    let mut board1: Vec<Vec<LocationState>> = data
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|f| match f {
-                    'L' => Empty,
-                    '#' => Occupied,
-                    '.' => Gap,
-                    _ => unreachable!(),
-                })
-                .collect()
-        })
-        .collect();
-
-    loop {
-        let (check_next, res, new_board) = update_board2(&board1);
+    for _ in 0..MAX_ROUNDS {
+        let (check_next, res, new_board) = update_board2(&board1, rules);
 
         if !check_next {
-            return res;
+            return Ok(res);
         }
 
         board1 = new_board;
     }
+
+    Err(Ex11Error::NotStable(MAX_ROUNDS))
 }
 
 fn update_board(
     base_board: &[Vec<LocationState>],
     result_board: &mut [Vec<LocationState>],
+    rules: &SeatingRules,
 ) -> (bool, usize) {
     use LocationState::*;
 
@@ -113,7 +288,7 @@ fn update_board(
 
     for row in 0..=last_row {
         for col in 0..=last_col {
-            let occupied = num_occupied_adjacent_cells(base_board, &(row as i32), &(col as i32));
+            let occupied = num_occupied_neighbours(base_board, row, col, rules.neighbourhood);
 
             match &base_board[row][col] {
                 Empty if occupied == 0 => {
@@ -121,7 +296,7 @@ fn update_board(
                     was_any_seat_changed = true;
                     occupied_seats_num += 1;
                 },
-                Occupied if occupied >= 4 => {
+                Occupied if occupied >= rules.tolerance => {
                     result_board[row][col] = Empty;
                     was_any_seat_changed = true;
                 },
@@ -139,86 +314,95 @@ fn update_board(
     (was_any_seat_changed, occupied_seats_num)
 }
 
-pub fn count_occupied_seats(data: &str) -> usize {
-    use LocationState::*;
-
-    let mut board1: Vec<Vec<LocationState>> = data
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|f| match f {
-                    'L' => Empty,
-                    '#' => Occupied,
-                    '.' => Gap,
-                    _ => unreachable!(),
-                })
-                .collect()
-        })
-        .collect();
+pub fn count_occupied_seats(data: &str, rules: &SeatingRules) -> Result<usize, Ex11Error> {
+    let mut board1 = parse_board(data)?;
 
     let mut board2 = board1.clone();
 
     let mut change_first = false;
 
-    loop {
+    for _ in 0..MAX_ROUNDS {
         let (check_next, res) = {
             if change_first {
                 change_first = false;
-                update_board(&board2, &mut board1)
+                update_board(&board2, &mut board1, rules)
             } else {
                 change_first = true;
-                update_board(&board1, &mut board2)
+                update_board(&board1, &mut board2, rules)
             }
         };
 
         if !check_next {
-            return res;
+            return Ok(res);
         }
     }
-}
-
-pub fn run<P>(path: P) -> anyhow::Result<()>
-where
-    P: AsRef<Path>,
-{
-    let data = get_data(path)?;
-
-    println!("Version1");
-    println!(
-        "There are {} occupied seats",
-        count_occupied_seats(data.as_str())
-    );
-
-    println!("Version2");
-    println!(
-        "There are {} occupied seats",
-        count_occupied_seats2(data.as_str())
-    );
 
-    Ok(())
+    Err(Ex11Error::NotStable(MAX_ROUNDS))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::{self, InputSource};
     use test_case::test_case;
 
-    #[test_case("data_files/ex11.txt" => (37, 37))]
-    fn test_ex11_counter_methods(s: &str) -> (usize, usize) {
-        let data = get_data(s).unwrap();
+    #[test_case("data_files/ex11.txt", SeatingRules::PART_ONE => (37, 37); "part one")]
+    #[test_case("data_files/ex11.txt", SeatingRules::PART_TWO => (26, 26); "part two")]
+    fn test_ex11_counter_methods(s: &str, rules: SeatingRules) -> (usize, usize) {
+        let data = InputSource::from(s).read().unwrap();
         (
-            count_occupied_seats(data.as_str()),
-            count_occupied_seats2(data.as_str()),
+            count_occupied_seats(data.as_str(), &rules).unwrap(),
+            count_occupied_seats2(data.as_str(), &rules).unwrap(),
         )
     }
 
+    #[test_case("LLLLL\nLLLLL\nLLLLL" => (7, 7); "wider than high")]
+    #[test_case("L\nL\nL\nL\nL\nL\nL\nL\nL\nL\nL\nL" => (12, 12); "higher than ten")]
+    fn test_ex11_board_dimensions(s: &str) -> (usize, usize) {
+        (
+            count_occupied_seats(s, &SeatingRules::PART_ONE).unwrap(),
+            count_occupied_seats2(s, &SeatingRules::PART_ONE).unwrap(),
+        )
+    }
+
+    #[test_case(".......#.\n...#.....\n.#.......\n.........\n..#L....#\n....#....\n.........\n#........\n...#.....", 4, 3 => 8; "sees eight")]
+    #[test_case(".............\n.L.L.#.#.#.#.\n.............", 1, 1 => 0; "empty seat blocks the view")]
+    #[test_case(".##.##.\n#.#.#.#\n##...##\n...L...\n##...##\n#.#.#.#\n.##.##.", 3, 3 => 0; "sees nothing")]
+    fn test_ex11_visible_neighbours(s: &str, row: usize, col: usize) -> u8 {
+        num_occupied_neighbours(&parse_board(s).unwrap(), row, col, Neighbourhood::Visible)
+    }
+
+    #[test_case("L.\nLx" => Err(Ex11Error::UnknownLocation{line: 2, location: 'x', text: "Lx".to_owned()}); "unknown location")]
+    #[test_case("L.\nL" => Err(Ex11Error::RaggedRow{line: 2, expected: 2, found: 1, text: "L".to_owned()}); "ragged row")]
+    #[test_case("" => Err(Ex11Error::EmptyBoard); "empty")]
+    fn test_ex11_parse_board_errors(s: &str) -> Result<usize, Ex11Error> {
+        Ok(parse_board(s)?.len())
+    }
+
+    #[test]
+    fn test_ex11_no_file() {
+        assert!(input::solve::<Day11VariantA>(&InputSource::from("aaa")).is_err())
+    }
+
     #[test]
-    fn test_ex11_run_no_file() {
-        assert!(run("aaa").is_err())
+    fn test_ex11_tolerance_above_neighbours() {
+        let rules = SeatingRules {
+            tolerance: 9,
+            ..SeatingRules::PART_ONE
+        };
+        let data = InputSource::from("data_files/ex11.txt").read().unwrap();
+
+        assert_eq!(count_occupied_seats(&data, &rules).unwrap(), 71);
     }
 
     #[test]
-    fn test_ex11_run_file_exists() {
-        assert!(!run("data_files/ex11.txt").is_err())
+    fn test_ex11_not_stable() {
+        let rules = SeatingRules {
+            tolerance: 1,
+            ..SeatingRules::PART_ONE
+        };
+
+        assert_eq!(count_occupied_seats("LL", &rules), Err(Ex11Error::NotStable(MAX_ROUNDS)));
+        assert_eq!(count_occupied_seats2("LL", &rules), Err(Ex11Error::NotStable(MAX_ROUNDS)));
     }
 }
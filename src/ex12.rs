@@ -1,59 +1,111 @@
-use std::fs;
-use std::path::Path;
 use std::str::FromStr;
+use thiserror::Error;
 
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 
+use super::diagnostic::{Diagnose, Locate};
+use super::part::PartTwo;
+
 pub struct Day12;
 
 impl DaySolver for Day12 {
-    type Output = f32;
+    type Output = u32;
 
     const INFO: DayInfo = DayInfo::with_day_and_file("day_12", "data_files/ex12.txt");
 
-    fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> { 
-        count_travel_distance(_s)
+    fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
+        count_travel_distance(_s, Navigation::Ship, false)
+    }
+}
+
+impl PartTwo for Day12 {
+    type OutputPartTwo = u32;
+
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+        count_travel_distance(s, Navigation::Waypoint, false)
+    }
+}
+
+/// Same as `Day12`, but rejects instructions which can't be parsed instead of skipping them.
+pub struct Day12Strict;
+
+impl DaySolver for Day12Strict {
+    type Output = u32;
+
+    const INFO: DayInfo =
+        DayInfo::with_day_and_file_and_variant("day_12", "data_files/ex12.txt", "strict");
+
+    fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
+        count_travel_distance(_s, Navigation::Ship, true)
+    }
+}
+
+impl PartTwo for Day12Strict {
+    type OutputPartTwo = u32;
+
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+        count_travel_distance(s, Navigation::Waypoint, true)
     }
 }
 
-#[derive(Debug, Default)]
-struct ShipDirection {
-    x: f32,
-    y: f32,
+/// East and north are the positive directions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
 }
 
-impl ShipDirection {
-    fn new_xy(x: f32, y: f32) -> Self {
+impl Point {
+    pub fn new(x: i32, y: i32) -> Self {
         Self { x, y }
     }
 
-    fn rotate(&mut self, angle: f32) {
-        let (sin, cos) = angle.to_radians().sin_cos();
-        let new_x = (self.x * cos - self.y * sin) * 100_f32;
-        let new_y = (self.y * cos + self.x * sin) * 100_f32;
+    /// Rotates around the origin, positive angles turn left.
+    fn rotate(self, angle: i32) -> Self {
+        match angle.rem_euclid(360) / 90 {
+            0 => self,
+            1 => Point::new(-self.y, self.x),
+            2 => Point::new(-self.x, -self.y),
+            _ => Point::new(self.y, -self.x),
+        }
+    }
 
-        self.x = new_x.round() / 100_f32;
-        self.y = new_y.round() / 100_f32;
+    fn shifted(self, x: i32, y: i32) -> Self {
+        Point::new(self.x + x, self.y + y)
     }
 
-    fn count_movement(&self, units: f32) -> (f32, f32) {
-        (self.x * units, self.y * units)
+    pub fn distance(&self) -> u32 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
     }
 }
 
-#[derive(Debug, Default)]
-struct Position {
-    x: f32,
-    y: f32,
-    direction: ShipDirection,
+/// What the `N`, `S`, `E` and `W` actions move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Navigation {
+    Ship,
+    Waypoint,
 }
 
-impl Position {
-    fn new_with_xy_direction(x: f32, y: f32) -> Self {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ship {
+    pub position:   Point,
+    /// Facing direction of the ship or its waypoint, `F` moves the ship by it.
+    pub heading:    Point,
+    pub navigation: Navigation,
+}
+
+impl Ship {
+    pub fn new(navigation: Navigation) -> Self {
+        let heading = match navigation {
+            Navigation::Ship => Point::new(1, 0),
+            Navigation::Waypoint => Point::new(10, 1),
+        };
+
         Self {
-            direction: ShipDirection::new_xy(x, y),
-            ..Default::default()
+            position: Point::default(),
+            heading,
+            navigation,
         }
     }
 
@@ -61,24 +113,52 @@ impl Position {
         use Move::*;
 
         let (x_delta, y_delta) = match action {
-            North(v) | South(v) => (0_f32, v as f32),
-            West(v) | East(v) => (v as f32, 0_f32),
+            North(v) | South(v) => (0, v),
+            West(v) | East(v) => (v, 0),
             Left(v) | Right(v) => {
-                self.direction.rotate(v as f32);
-                (0_f32, 0_f32)
+                self.heading = self.heading.rotate(v);
+                (0, 0)
+            },
+            Forward(v) => {
+                self.position = self.position.shifted(self.heading.x * v, self.heading.y * v);
+                (0, 0)
             },
-            Forward(v) => self.direction.count_movement(v as f32),
         };
 
-        self.x += x_delta;
-        self.y += y_delta;
+        match self.navigation {
+            Navigation::Ship => self.position = self.position.shifted(x_delta, y_delta),
+            Navigation::Waypoint => self.heading = self.heading.shifted(x_delta, y_delta),
+        }
     }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex12Error {
+    #[error("unknown action in `{0}`")]
+    UnknownAction(String),
+
+    #[error("invalid action value in `{0}`")]
+    InvalidValue(String),
 
-    fn distance(&self) -> f32 {
-        self.x.abs() + self.y.abs()
+    #[error("turn by other angle than a multiple of 90 degrees in `{0}`")]
+    InvalidAngle(String),
+}
+
+impl Locate for Ex12Error {
+    fn line(&self) -> Option<usize> {
+        None
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex12Error::UnknownAction(text)
+            | Ex12Error::InvalidValue(text)
+            | Ex12Error::InvalidAngle(text) => Some(text.to_owned()),
+        }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Move {
     North(i32),
     South(i32),
@@ -90,13 +170,21 @@ enum Move {
 }
 
 impl FromStr for Move {
-    type Err = anyhow::Error;
+    type Err = Ex12Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         use Move::*;
 
-        let action = &s[0..1];
-        let value = &s[1..].parse::<i32>()?;
+        let Some(action) = s.get(0..1) else {
+            return Err(Ex12Error::UnknownAction(s.to_owned()));
+        };
+        let value = &s[1..]
+            .parse::<i32>()
+            .map_err(|_| Ex12Error::InvalidValue(s.to_owned()))?;
+
+        if matches!(action, "L" | "R") && value % 90 != 0 {
+            return Err(Ex12Error::InvalidAngle(s.to_owned()));
+        }
 
         match action {
             "N" => Ok(North(*value)),
@@ -106,66 +194,122 @@ impl FromStr for Move {
             "L" => Ok(Left(*value)),
             "R" => Ok(Right(-*value)),
             "F" => Ok(Forward(*value)),
-            _ => Err(anyhow::anyhow!("unknown action {}", action)),
+            _ => Err(Ex12Error::UnknownAction(s.to_owned())),
         }
     }
 }
 
-pub fn count_travel_distance(data: &str) -> anyhow::Result<f32> {
-    let final_position = data
-        .lines()
-        .filter_map(|line| line.parse::<Move>().ok())
-        .fold(
-            Position::new_with_xy_direction(1_f32, 0_f32),
-            |mut position, action| {
-                position.change(action);
-                position
-            },
-        );
+/// Moves with their line numbers, lines which can't be parsed are skipped unless `strict`.
+fn parse_moves(data: &str, strict: bool) -> anyhow::Result<Vec<(usize, Move)>> {
+    let mut moves: Vec<(usize, Move)> = Vec::new();
+
+    for (line_num, line) in data.lines().enumerate() {
+        match line.parse::<Move>() {
+            Ok(action) => moves.push((line_num + 1, action)),
+            Err(err) if strict => return Err(err).diagnose_line(data, line_num + 1),
+            Err(_) => {},
+        }
+    }
 
-    Ok(final_position.distance())
+    Ok(moves)
 }
 
-fn get_data<P>(path: P) -> anyhow::Result<String>
-where
-    P: AsRef<Path>,
-{
-    let contents = fs::read_to_string(path)?;
-    Ok(contents)
+/// State of the ship after an instruction from given line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceStep {
+    pub line: usize,
+    pub ship: Ship,
 }
 
-pub fn run<P>(path: P) -> anyhow::Result<()>
-where
-    P: AsRef<Path>,
-{
-    let data = get_data(path)?;
-    println!("movement: {:?}", count_travel_distance(data.as_str())?);
-    Ok(())
+pub fn trace(data: &str, navigation: Navigation, strict: bool) -> anyhow::Result<Vec<TraceStep>> {
+    let mut ship = Ship::new(navigation);
+
+    Ok(parse_moves(data, strict)?
+        .into_iter()
+        .map(|(line, action)| {
+            ship.change(action);
+            TraceStep { line, ship }
+        })
+        .collect())
+}
+
+pub fn count_travel_distance(
+    data: &str,
+    navigation: Navigation,
+    strict: bool,
+) -> anyhow::Result<u32> {
+    let final_ship = parse_moves(data, strict)?
+        .into_iter()
+        .fold(Ship::new(navigation), |mut ship, (_, action)| {
+            ship.change(action);
+            ship
+        });
+
+    Ok(final_ship.position.distance())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::{self, InputSource};
+    use test_case::test_case;
 
-    #[test]
-    fn test_ex12_count_travel_distance() {
-        let data = get_data("data_files/ex12.txt").unwrap();
-        assert_eq!(count_travel_distance(data.as_str()).unwrap(), 25.0);
+    #[test_case("data_files/ex12.txt" => 25)]
+    #[test_case("data_files/ex12_mydata.txt" => 41)]
+    #[test_case("data_files/ex12_invalid.txt" => 18)]
+    fn test_ex12_count_travel_distance(s: &str) -> u32 {
+        input::solve::<Day12>(&InputSource::from(s)).unwrap()
+    }
+
+    #[test_case("data_files/ex12.txt" => 286)]
+    #[test_case("data_files/ex12_invalid.txt" => 244)]
+    fn test_ex12_waypoint_distance(s: &str) -> u32 {
+        let data = InputSource::from(s).read().unwrap();
+        Day12::solution_part_two(&data).unwrap()
+    }
+
+    #[test_case("data_files/ex12_invalid.txt" => Ex12Error::UnknownAction("X7".to_owned()); "unknown action")]
+    #[test_case("data_files/ex12_mydata.txt" => Ex12Error::InvalidAngle("L45".to_owned()); "invalid angle")]
+    fn test_ex12_strict(s: &str) -> Ex12Error {
+        let err = input::solve::<Day12Strict>(&InputSource::from(s)).unwrap_err();
+        err.downcast().unwrap()
+    }
 
-        let data = get_data("data_files/ex12_mydata.txt").unwrap();
-        assert_eq!(count_travel_distance(data.as_str()).unwrap(), 41.84);
+    #[test_case("F10" => Ok(Move::Forward(10)); "forward")]
+    #[test_case("R90" => Ok(Move::Right(-90)); "right")]
+    #[test_case("L270" => Ok(Move::Left(270)); "left")]
+    #[test_case("R45" => Err(Ex12Error::InvalidAngle("R45".to_owned())); "invalid angle")]
+    #[test_case("X10" => Err(Ex12Error::UnknownAction("X10".to_owned())); "unknown action")]
+    #[test_case("ŁX" => Err(Ex12Error::UnknownAction("ŁX".to_owned())); "non ascii action")]
+    #[test_case("" => Err(Ex12Error::UnknownAction("".to_owned())); "empty")]
+    #[test_case("Fx" => Err(Ex12Error::InvalidValue("Fx".to_owned())); "invalid value")]
+    fn test_ex12_move_from_str(s: &str) -> Result<Move, Ex12Error> {
+        s.parse::<Move>()
+    }
 
-        let data = get_data("data_files/ex12_invalid.txt").unwrap();
-        assert_eq!(count_travel_distance(data.as_str()).unwrap(), 18.0);
+    #[test_case(90 => Point::new(-1, 2); "left")]
+    #[test_case(-90 => Point::new(1, -2); "right")]
+    #[test_case(180 => Point::new(-2, -1); "around")]
+    #[test_case(-270 => Point::new(-1, 2); "three quarters right")]
+    #[test_case(360 => Point::new(2, 1); "full turn")]
+    fn test_ex12_rotate(angle: i32) -> Point {
+        Point::new(2, 1).rotate(angle)
     }
 
     #[test]
-    fn test_ex12_run_no_file() {
-        assert!(run("aaa").is_err())
+    fn test_ex12_trace() {
+        let data = InputSource::from("data_files/ex12.txt").read().unwrap();
+        let steps = trace(&data, Navigation::Waypoint, true).unwrap();
+
+        assert_eq!(steps.len(), 5);
+        assert_eq!(steps[0].line, 1);
+        assert_eq!(steps[0].ship.position, Point::new(100, 10));
+        assert_eq!(steps[3].ship.heading, Point::new(4, -10));
+        assert_eq!(steps[4].ship.position, Point::new(214, -72));
     }
 
     #[test]
-    fn test_ex12_run_file_exists() {
-        assert!(!run("data_files/ex12.txt").is_err())
+    fn test_ex12_no_file() {
+        assert!(input::solve::<Day12>(&InputSource::from("aaa")).is_err())
     }
-}
\ No newline at end of file
+}
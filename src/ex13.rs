@@ -1,5 +1,9 @@
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
+use thiserror::Error;
+
+use super::diagnostic::{Diagnose, Locate};
+use super::part::PartTwo;
 
 // -----------------------------------------------------------------------------
 
@@ -12,22 +16,124 @@ impl DaySolver for Day13VariantA {
         DayInfo::with_day_and_file_and_variant("day_13", "data_files/ex13.txt", "n^2");
 
     fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
-        let (arrival_time, buses) = get_data();
-        Ok(get_bus_mult_minutes2(arrival_time, &buses))
+        let notes = parse_notes(_s).diagnose(_s)?;
+        Ok(get_bus_mult_minutes2(notes.arrival_time, &notes.ids()))
+    }
+}
+
+impl PartTwo for Day13VariantA {
+    type OutputPartTwo = u128;
+
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+        let notes = parse_notes(s).diagnose(s)?;
+        Ok(earliest_timestamp(&notes)?)
     }
 }
 
-pub fn get_data() -> (u32, Vec<u32>) {
-    let input = "7,13,x,x,59,x,31,19";
-    let arrival_time = 939;
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex13Error {
+    #[error("expected arrival time and buses in two lines")]
+    MissingLines,
+
+    #[error("line 1: invalid arrival time `{0}`")]
+    InvalidArrivalTime(String),
+
+    #[error("line 2: invalid bus id `{0}`")]
+    InvalidBusId(String),
+
+    #[error("line 2: no bus in service")]
+    NoBuses,
+
+    #[error("no timestamp exists, bus {bus} at offset {offset} conflicts with the earlier buses")]
+    NoTimestamp { bus: u32, offset: usize },
+
+    #[error("timestamp does not fit into 128 bits")]
+    TimestampOverflow,
+}
+
+impl Locate for Ex13Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex13Error::MissingLines
+            | Ex13Error::NoTimestamp { .. }
+            | Ex13Error::TimestampOverflow => None,
+            Ex13Error::InvalidArrivalTime(_) => Some(1),
+            Ex13Error::InvalidBusId(_) | Ex13Error::NoBuses => Some(2),
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex13Error::InvalidArrivalTime(text) | Ex13Error::InvalidBusId(text) => {
+                Some(text.to_owned())
+            },
+            _ => None,
+        }
+    }
+}
 
-    let buses: Vec<u32> = input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bus {
+    pub id:     u32,
+    /// Minutes after the timestamp the bus should depart, its slot in the schedule.
+    pub offset: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Notes {
+    pub arrival_time: u32,
+    /// Schedule slots, `None` stands for `x`.
+    pub slots:        Vec<Option<u32>>,
+}
+
+impl Notes {
+    pub fn buses(&self) -> impl Iterator<Item = Bus> + '_ {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(offset, slot)| slot.map(|id| Bus { id, offset }))
+    }
+
+    /// Ids of buses in service.
+    pub fn ids(&self) -> Vec<u32> {
+        self.slots.iter().flatten().copied().collect()
+    }
+}
+
+pub fn parse_notes(s: &str) -> Result<Notes, Ex13Error> {
+    let mut lines = s.lines();
+    let (Some(arrival_time), Some(buses)) = (lines.next(), lines.next()) else {
+        return Err(Ex13Error::MissingLines);
+    };
+
+    let arrival_time = arrival_time
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Ex13Error::InvalidArrivalTime(arrival_time.to_owned()))?;
+
+    let slots: Vec<Option<u32>> = buses
+        .trim()
         .split(',')
-        .filter(|f| *f != "x")
-        .map(|num| num.parse::<u32>().unwrap())
-        .collect();
+        .map(|bus| {
+            if bus == "x" {
+                return Ok(None);
+            }
+
+            match bus.parse::<u32>() {
+                Ok(id) if id > 0 => Ok(Some(id)),
+                _ => Err(Ex13Error::InvalidBusId(bus.to_owned())),
+            }
+        })
+        .collect::<Result<_, _>>()?;
+
+    if slots.iter().all(Option::is_none) {
+        return Err(Ex13Error::NoBuses);
+    }
 
-    (arrival_time, buses)
+    Ok(Notes {
+        arrival_time,
+        slots,
+    })
 }
 
 pub fn get_bus_mult_minutes(arrival_time: u32, buses: &[u32]) -> u32 {
@@ -76,22 +182,88 @@ pub fn get_bus_mult_minutes3(arrival_time: u32, buses: &[u32]) -> u32 {
     (waiting_time) * bus_id
 }
 
-pub fn run() -> anyhow::Result<()> {
-    let (arrival_time, buses) = get_data();
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
 
-    println!("Version1");
-    println!(
-        "Bus ID mult by minutes needed to wait: {}",
-        get_bus_mult_minutes(arrival_time, &buses)
-    );
+/// Inverse of `a` modulo `modulus`, both have to be coprime.
+fn mod_inverse(a: u64, modulus: u64) -> u64 {
+    let (mut old_r, mut r) = (i128::from(a), i128::from(modulus));
+    let (mut old_s, mut s) = (1_i128, 0_i128);
 
-    println!("Version2");
-    println!(
-        "Bus ID mult by minutes needed to wait: {}",
-        get_bus_mult_minutes2(arrival_time, &buses)
-    );
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+    }
+
+    old_s.rem_euclid(i128::from(modulus)) as u64
+}
 
-    Ok(())
+/// Joins `x ≡ residue (mod modulus)` congruences, moduli don't have to be coprime.
+fn merge_congruences(
+    (residue, modulus): (u128, u128),
+    (other_residue, other_modulus): (u64, u64),
+) -> Result<Option<(u128, u128)>, Ex13Error> {
+    let other_modulus = u128::from(other_modulus);
+    let common = gcd(modulus, other_modulus);
+    let difference = (u128::from(other_residue) + other_modulus - residue % other_modulus)
+        % other_modulus;
+
+    if difference % common != 0 {
+        return Ok(None);
+    }
+
+    // residue + modulus * k ≡ other_residue, solved for k modulo other_modulus / common
+    let reduced = (other_modulus / common) as u64;
+    let inverse = mod_inverse(((modulus / common) % u128::from(reduced)) as u64, reduced);
+    let k = (difference / common) * u128::from(inverse) % u128::from(reduced);
+
+    let lcm = (modulus / common)
+        .checked_mul(other_modulus)
+        .ok_or(Ex13Error::TimestampOverflow)?;
+    let merged = modulus
+        .checked_mul(k)
+        .and_then(|shift| shift.checked_add(residue))
+        .ok_or(Ex13Error::TimestampOverflow)?;
+
+    Ok(Some((merged % lcm, lcm)))
+}
+
+/// Chinese remainder theorem for `(residue, modulus)` pairs, `None` when no number satisfies
+/// all of them. Returns the smallest solution and the modulus of all solutions.
+pub fn crt(congruences: &[(u64, u64)]) -> Result<Option<(u128, u128)>, Ex13Error> {
+    let mut res: (u128, u128) = (0, 1);
+
+    for congruence in congruences {
+        match merge_congruences(res, *congruence)? {
+            Some(merged) => res = merged,
+            None => return Ok(None),
+        }
+    }
+
+    Ok(Some(res))
+}
+
+/// First timestamp at which every bus departs at its offset.
+pub fn earliest_timestamp(notes: &Notes) -> Result<u128, Ex13Error> {
+    let mut res: (u128, u128) = (0, 1);
+
+    for bus in notes.buses() {
+        let id = u64::from(bus.id);
+        let residue = (id - bus.offset as u64 % id) % id;
+
+        res = merge_congruences(res, (residue, id))?.ok_or(Ex13Error::NoTimestamp {
+            bus:    bus.id,
+            offset: bus.offset,
+        })?;
+    }
+
+    Ok(res.0)
 }
 
 #[cfg(test)]
@@ -114,4 +286,45 @@ mod tests {
     fn test_ex13_get_bus_mult_minutes2(arrival_time: u32, buses: Vec<u32>) -> u32 {
         get_bus_mult_minutes2(arrival_time, &buses)
     }
+
+    #[test_case("939\n7,13,x,x,59,x,31,19" => Ok((939, vec![7, 13, 59, 31, 19])); "valid")]
+    #[test_case("939" => Err(Ex13Error::MissingLines); "missing buses")]
+    #[test_case("soon\n7,13" => Err(Ex13Error::InvalidArrivalTime("soon".to_owned())); "arrival time")]
+    #[test_case("939\n7,y" => Err(Ex13Error::InvalidBusId("y".to_owned())); "bus id")]
+    #[test_case("939\n7,0" => Err(Ex13Error::InvalidBusId("0".to_owned())); "zero bus id")]
+    #[test_case("939\nx,x" => Err(Ex13Error::NoBuses); "no buses")]
+    fn test_ex13_parse_notes(s: &str) -> Result<(u32, Vec<u32>), Ex13Error> {
+        parse_notes(s).map(|notes| (notes.arrival_time, notes.ids()))
+    }
+
+    #[test]
+    fn test_ex13_bus_offsets() {
+        let notes = parse_notes("939\n7,13,x,x,59").unwrap();
+
+        assert_eq!(notes.slots, vec![Some(7), Some(13), None, None, Some(59)]);
+        assert_eq!(
+            notes.buses().map(|bus| (bus.id, bus.offset)).collect::<Vec<_>>(),
+            vec![(7, 0), (13, 1), (59, 4)]
+        );
+    }
+
+    #[test_case("7,13,x,x,59,x,31,19" => Ok(1068781); "example")]
+    #[test_case("17,x,13,19" => Ok(3417))]
+    #[test_case("67,7,x,59,61" => Ok(1261476))]
+    #[test_case("1789,37,47,1889" => Ok(1202161486))]
+    #[test_case("4,x,6" => Ok(4); "not coprime")]
+    #[test_case("3,x,x,x,x" => Ok(0); "single bus")]
+    #[test_case("4,6" => Err(Ex13Error::NoTimestamp{bus: 6, offset: 1}); "no timestamp")]
+    fn test_ex13_earliest_timestamp(buses: &str) -> Result<u128, Ex13Error> {
+        earliest_timestamp(&parse_notes(&format!("0\n{}", buses)).unwrap())
+    }
+
+    #[test_case(&[(2, 3), (3, 5), (2, 7)] => Ok(Some((23, 105))); "coprime")]
+    #[test_case(&[(1, 4), (3, 6)] => Ok(Some((9, 12))); "common factor")]
+    #[test_case(&[(0, 4), (1, 6)] => Ok(None); "no solution")]
+    #[test_case(&[] => Ok(Some((0, 1))); "no congruences")]
+    #[test_case(&[(0, u64::MAX), (1, u64::MAX - 1), (2, u64::MAX - 2)] => Err(Ex13Error::TimestampOverflow); "overflow")]
+    fn test_ex13_crt(congruences: &[(u64, u64)]) -> Result<Option<(u128, u128)>, Ex13Error> {
+        crt(congruences)
+    }
 }
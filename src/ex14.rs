@@ -2,6 +2,10 @@ use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 use std::collections::HashMap;
 use std::str::FromStr;
+use thiserror::Error;
+
+use super::diagnostic::{Diagnose, Locate};
+use super::part::PartTwo;
 
 // -----------------------------------------------------------------------------
 
@@ -9,28 +13,196 @@ type MemAddress = u64;
 type MemValue = u64;
 type MaskValue = u64;
 
+const MASK_LEN: usize = 36;
+const MASK_BITS: MaskValue = (1 << MASK_LEN) - 1;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex14Error {
+    #[error("program has to start with a mask")]
+    MissingMask,
+
+    #[error("invalid mask `{0}` (expected 36 of `X`, `0` or `1`)")]
+    InvalidMask(String),
+
+    #[error("invalid instruction `{0}`")]
+    InvalidInstruction(String),
+
+    #[error("line {line}: {source}")]
+    InLine {
+        line:   usize,
+        source: Box<Ex14Error>,
+    },
+}
+
+impl Locate for Ex14Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex14Error::InLine { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex14Error::InLine { source, .. } => source.fragment(),
+            Ex14Error::InvalidMask(text) | Ex14Error::InvalidInstruction(text) => {
+                Some(text.to_owned())
+            },
+            Ex14Error::MissingMask => None,
+        }
+    }
+}
+
+impl Ex14Error {
+    pub fn in_line(self, line_num: usize) -> Self {
+        Ex14Error::InLine {
+            line:   line_num + 1,
+            source: Box::new(self),
+        }
+    }
+}
+
+/// Address with floating bits, which stands for every address matching it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FloatingAddress {
+    fixed:    MemAddress,
+    floating: MaskValue,
+}
+
+impl FloatingAddress {
+    pub fn new(address: MemAddress, floating: MaskValue) -> Self {
+        Self {
+            fixed: address & !floating,
+            floating,
+        }
+    }
+
+    /// Number of matching addresses.
+    pub fn count(&self) -> u64 {
+        1 << self.floating.count_ones()
+    }
+
+    /// Every matching address, starting from the lowest one.
+    pub fn addresses(&self) -> impl Iterator<Item = MemAddress> {
+        let (fixed, floating) = (self.fixed, self.floating);
+        let mut bits = Some(0);
+
+        std::iter::from_fn(move || {
+            let current: MaskValue = bits?;
+            let next = current.wrapping_sub(floating) & floating;
+            bits = (next != 0).then_some(next);
+
+            Some(fixed | current)
+        })
+    }
+
+    fn overlaps(&self, other: &FloatingAddress) -> bool {
+        (self.fixed ^ other.fixed) & !self.floating & !other.floating == 0
+    }
+
+    /// Addresses matching `self` but not `other`, split into disjoint floating addresses.
+    fn subtract(self, other: &FloatingAddress) -> Vec<FloatingAddress> {
+        if !self.overlaps(other) {
+            return vec![self];
+        }
+
+        let mut res: Vec<FloatingAddress> = Vec::new();
+        let mut rest = self;
+        let mut bits = self.floating & !other.floating;
+
+        // rest keeps bits fixed as in `other`, every split off part has one bit opposite
+        while bits != 0 {
+            let bit = bits & bits.wrapping_neg();
+            bits &= bits - 1;
+
+            rest.floating &= !bit;
+            res.push(FloatingAddress {
+                fixed:    rest.fixed | (!other.fixed & bit),
+                floating: rest.floating,
+            });
+            rest.fixed |= other.fixed & bit;
+        }
+
+        res
+    }
+}
+
+/// How `mem` instructions apply the mask.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Decoder {
+    /// Mask overwrites bits of the written value.
+    #[default]
+    Version1,
+    /// Mask sets bits of the address, `X` bits float and the write goes to every match.
+    Version2,
+}
+
 #[derive(Default)]
 pub struct Processor {
-    mem:   HashMap<MemAddress, MemValue>,
-    set:   MaskValue,
-    unset: MaskValue,
+    mem:          HashMap<MemAddress, MemValue>,
+    floating_mem: Vec<(FloatingAddress, MemValue)>,
+    set:          MaskValue,
+    unset:        MaskValue,
+    decoder:      Decoder,
 }
 
 impl Processor {
+    pub fn new(decoder: Decoder) -> Self {
+        Self {
+            decoder,
+            ..Default::default()
+        }
+    }
+
     pub fn process(&mut self, instruction: Instruction) {
         match instruction {
             Instruction::Mask(mask_set, mask_unset) => {
                 self.set = mask_set;
                 self.unset = mask_unset;
             },
-            Instruction::Mem(address, value) => {
+            Instruction::Mem(address, value) if self.decoder == Decoder::Version1 => {
                 self.mem.insert(address, value & self.unset | self.set);
             },
+            Instruction::Mem(address, value) => {
+                let floating = self.unset & !self.set & MASK_BITS;
+                let target = FloatingAddress::new(address | self.set, floating);
+
+                // older writes keep only addresses not overwritten by this one
+                self.floating_mem = std::mem::take(&mut self.floating_mem)
+                    .into_iter()
+                    .flat_map(|(written, old)| {
+                        written
+                            .subtract(&target)
+                            .into_iter()
+                            .map(move |part| (part, old))
+                    })
+                    .collect();
+                self.floating_mem.push((target, value));
+            },
         }
     }
 
-    pub fn output_value(&self) -> u64 {
-        self.mem.values().sum::<u64>()
+    /// Written memory, addresses written by the version 2 decoder stay floating.
+    pub fn memory(&self) -> Vec<(FloatingAddress, MemValue)> {
+        match self.decoder {
+            Decoder::Version1 => self
+                .mem
+                .iter()
+                .map(|(address, value)| (FloatingAddress::new(*address, 0), *value))
+                .collect(),
+            Decoder::Version2 => self.floating_mem.to_vec(),
+        }
+    }
+
+    pub fn output_value(&self) -> u128 {
+        match self.decoder {
+            Decoder::Version1 => self.mem.values().map(|value| u128::from(*value)).sum(),
+            Decoder::Version2 => self
+                .floating_mem
+                .iter()
+                .map(|(address, value)| u128::from(address.count()) * u128::from(*value))
+                .sum(),
+        }
     }
 }
 
@@ -39,54 +211,63 @@ impl Processor {
 pub struct Day14VariantA;
 
 impl DaySolver for Day14VariantA {
-    type Output = u64;
+    type Output = u128;
 
     const INFO: DayInfo =
         DayInfo::with_day_and_file_and_variant("day_14", "data_files/ex14.txt", "base");
 
     fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
-        let (set, unset, dict) = prepare_input(_s)?;
+        Ok(run_program(&parse_program(_s)?, Decoder::Version1).output_value())
+    }
+}
 
-        let result: u64 = dict
-            .iter()
-            .map(|(_, value)| {
-                let mut res = *value;
-                res |= set;
-                res &= unset;
-                res as u64
-            })
-            .sum();
+impl PartTwo for Day14VariantA {
+    type OutputPartTwo = u128;
 
-        Ok(result)
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+        Ok(run_program(&parse_program(s)?, Decoder::Version2).output_value())
     }
 }
 
-pub fn prepare_input(s: &str) -> anyhow::Result<(u64, u64, HashMap<u64, u64>)> {
-    let mut input_lines = s.lines();
-    let mask: &str = input_lines.next().unwrap().split(" = ").nth(1).unwrap();
+/// Instructions of the whole program, which has to start with a mask.
+pub fn parse_program(s: &str) -> anyhow::Result<Vec<Instruction>> {
+    let mut program: Vec<Instruction> = Vec::new();
 
-    let mut memorized: HashMap<u64, u64> = HashMap::new();
+    for (line_num, line) in s.lines().enumerate() {
+        let instruction = line
+            .parse::<Instruction>()
+            .map_err(|err| err.in_line(line_num))
+            .diagnose(s)?;
+
+        if program.is_empty() && matches!(instruction, Instruction::Mem(..)) {
+            return Err(Ex14Error::MissingMask.in_line(line_num)).diagnose(s);
+        }
 
-    for line in input_lines {
-        let (key_part, value_part) = line.split_once(" = ").unwrap();
-        let num = value_part.parse::<u64>()?;
-        let key = key_part
-            .split('[')
-            .nth(1)
-            .unwrap()
-            .split(']')
-            .next()
-            .unwrap()
-            .parse::<u64>()?;
-        memorized.insert(key, num);
+        program.push(instruction);
     }
 
-    let (set, unset) = process_mask(mask)?;
+    if program.is_empty() {
+        return Err(Ex14Error::MissingMask.into());
+    }
+
+    Ok(program)
+}
+
+pub fn run_program(program: &[Instruction], decoder: Decoder) -> Processor {
+    let mut processor = Processor::new(decoder);
+
+    for instruction in program {
+        processor.process(*instruction);
+    }
 
-    Ok((set, unset, memorized))
+    processor
 }
 
-pub fn process_mask(mask: &str) -> anyhow::Result<(u64, u64)> {
+pub fn process_mask(mask: &str) -> Result<(u64, u64), Ex14Error> {
+    if mask.len() != MASK_LEN || mask.chars().any(|c| !matches!(c, 'X' | '0' | '1')) {
+        return Err(Ex14Error::InvalidMask(mask.to_owned()));
+    }
+
     let binary_base: usize = 2;
     let (set, unset) = mask
         .chars()
@@ -103,29 +284,44 @@ pub fn process_mask(mask: &str) -> anyhow::Result<(u64, u64)> {
     Ok((set as u64, !unset as u64))
 }
 
+fn scan_mask(s: &str) -> Result<String, text_io::Error> {
+    use text_io::try_scan;
+
+    let mask: String;
+    try_scan!(s.bytes() => "mask = {}", mask);
+
+    Ok(mask)
+}
+
+fn scan_mem(s: &str) -> Result<(u64, u64), text_io::Error> {
+    use text_io::try_scan;
+
+    let address_value: u64;
+    let mem_value: u64;
+    try_scan!(s.bytes() => "mem[{}] = {}", address_value, mem_value);
+
+    Ok((address_value, mem_value))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Instruction {
     Mask(u64, u64),
     Mem(u64, u64),
 }
 
 impl FromStr for Instruction {
-    type Err = anyhow::Error;
+    type Err = Ex14Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        use text_io::try_scan;
+        let invalid = |_| Ex14Error::InvalidInstruction(s.to_owned());
 
         let instruction = if s.starts_with("ma") {
-            let mask: String;
-
-            try_scan!(s.bytes() => "mask = {}", mask);
+            let mask = scan_mask(s).map_err(invalid)?;
 
             let (set, unset) = process_mask(mask.trim())?;
             Self::Mask(set, unset)
         } else {
-            let address_value: u64;
-            let mem_value: u64;
-
-            try_scan!(s.bytes() => "mem[{}] = {}", address_value, mem_value);
+            let (address_value, mem_value) = scan_mem(s).map_err(invalid)?;
 
             Self::Mem(address_value, mem_value)
         };
@@ -137,7 +333,7 @@ impl FromStr for Instruction {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+    use test_case::test_case;
 
     #[test]
     fn ex14_process_mask() {
@@ -146,15 +342,100 @@ mod tests {
     }
 
     #[test]
-    fn ex14_process_input() {
+    fn ex14_parse_program() {
         use aoc_utils::read_to_string;
-        let dict: HashMap<u64, u64> = HashMap::from([(7, 101), (8, 0)]);
+
+        assert_eq!(
+            parse_program(&read_to_string("data_files/ex14.txt").unwrap()).unwrap(),
+            vec![
+                Instruction::Mask(64, 18446744073709551613),
+                Instruction::Mem(8, 11),
+                Instruction::Mem(7, 101),
+                Instruction::Mem(8, 0),
+            ]
+        );
+    }
+
+    #[test_case(Decoder::Version1 => 165; "version 1")]
+    #[test_case(Decoder::Version2 => 1735166787584; "version 2 with 34 floating bits")]
+    fn ex14_run_program(decoder: Decoder) -> u128 {
+        let input = aoc_utils::read_to_string("data_files/ex14.txt").unwrap();
+        run_program(&parse_program(&input).unwrap(), decoder).output_value()
+    }
+
+    const MULTIPLE_MASKS: &str = "mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1";
+
+    #[test_case(Decoder::Version1 => 51; "version 1")]
+    #[test_case(Decoder::Version2 => 208; "version 2")]
+    fn ex14_multiple_masks(decoder: Decoder) -> u128 {
+        run_program(&parse_program(MULTIPLE_MASKS).unwrap(), decoder).output_value()
+    }
+
+    #[test]
+    fn ex14_floating_addresses() {
+        let address = FloatingAddress::new(26, 0b1011);
+
+        assert_eq!(address.count(), 8);
+        assert_eq!(
+            address.addresses().collect::<Vec<_>>(),
+            vec![16, 17, 18, 19, 24, 25, 26, 27]
+        );
+    }
+
+    #[test]
+    fn ex14_floating_address_subtract() {
+        let parts = FloatingAddress::new(0, 0b11).subtract(&FloatingAddress::new(1, 0));
+        let mut addresses: Vec<u64> = parts.iter().flat_map(|part| part.addresses()).collect();
+        addresses.sort_unstable();
+
+        assert_eq!(addresses, vec![0, 2, 3]);
+        assert_eq!(
+            FloatingAddress::new(4, 0b11).subtract(&FloatingAddress::new(1, 0)),
+            vec![FloatingAddress::new(4, 0b11)]
+        );
+    }
+
+    #[test]
+    fn ex14_memory_report() {
+        let processor = run_program(&parse_program(MULTIPLE_MASKS).unwrap(), Decoder::Version2);
+        let written: u64 = processor
+            .memory()
+            .iter()
+            .map(|(address, _)| address.count())
+            .sum();
+
+        assert_eq!(written, 10);
+    }
+
+    #[test_case("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X" => Ok(Instruction::Mask(64, !2)); "mask")]
+    #[test_case("mem[8] = 11" => Ok(Instruction::Mem(8, 11)); "mem")]
+    #[test_case("mask = XX2" => Err(Ex14Error::InvalidMask("XX2".to_owned())); "invalid mask")]
+    #[test_case("mem[8] = x" => Err(Ex14Error::InvalidInstruction("mem[8] = x".to_owned())); "invalid value")]
+    #[test_case("nop" => Err(Ex14Error::InvalidInstruction("nop".to_owned())); "unknown instruction")]
+    fn ex14_instruction_from_str(s: &str) -> Result<Instruction, Ex14Error> {
+        s.parse::<Instruction>()
+    }
+
+    #[test]
+    fn ex14_parse_program_reports_line() {
+        let input = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X\nmem[8] = x";
+        let err = parse_program(input).unwrap_err();
+
         assert_eq!(
-            prepare_input(&read_to_string("data_files/ex14.txt").unwrap()).unwrap(),
-            (64, 18446744073709551613, dict)
+            err.downcast::<Ex14Error>().unwrap(),
+            Ex14Error::InvalidInstruction("mem[8] = x".to_owned()).in_line(1)
         );
     }
 
+    #[test_case("mem[8] = 11" => Ex14Error::MissingMask.in_line(0); "write before mask")]
+    #[test_case("" => Ex14Error::MissingMask; "empty program")]
+    fn ex14_parse_program_missing_mask(s: &str) -> Ex14Error {
+        parse_program(s).unwrap_err().downcast().unwrap()
+    }
+
     #[test]
     fn data_from_default_file() {
         assert_eq!(Day14VariantA::solve_default_file().unwrap(), 165)
@@ -2,34 +2,169 @@ pub struct Day14VariantB;
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 
+use super::ex14::{parse_program, Decoder, Instruction};
+use super::part::PartTwo;
+
 impl DaySolver for Day14VariantB {
-    type Output = u64;
+    type Output = u128;
 
     const INFO: DayInfo =
         DayInfo::with_day_and_file_and_variant("day_14", "data_files/ex14.txt", "two vectors");
 
     fn solution(s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
-        use super::ex14::{Instruction, Processor};
-
-        let sum = s
-            .lines()
-            .filter_map(|line| line.parse::<Instruction>().ok())
-            .fold(Processor::default(), |mut processor, instruction| {
-                processor.process(instruction);
-                processor
+        Ok(run(&parse_program(s)?, Decoder::Version1).sum())
+    }
+}
+
+impl PartTwo for Day14VariantB {
+    type OutputPartTwo = u128;
+
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+        Ok(run(&parse_program(s)?, Decoder::Version2).sum())
+    }
+}
+
+const MASK_LEN: usize = 36;
+
+/// Address bits from the lowest one, `None` is a floating bit matching both values.
+type Pattern = [Option<bool>; 64];
+
+fn exact(address: u64) -> Pattern {
+    std::array::from_fn(|bit| Some(address >> bit & 1 == 1))
+}
+
+fn decoded(address: u64, set: u64, unset: u64) -> Pattern {
+    std::array::from_fn(|bit| {
+        if set >> bit & 1 == 1 {
+            Some(true)
+        } else if bit >= MASK_LEN || unset >> bit & 1 == 0 {
+            Some(address >> bit & 1 == 1)
+        } else {
+            None
+        }
+    })
+}
+
+fn overlap(pattern: &Pattern, other: &Pattern) -> bool {
+    pattern.iter().zip(other).all(|bits| match bits {
+        (Some(bit), Some(other_bit)) => bit == other_bit,
+        _ => true,
+    })
+}
+
+/// Number of addresses matching `pattern` and none of the `later` ones.
+fn count_unique(pattern: Pattern, later: &[Pattern]) -> u64 {
+    let Some(index) = later.iter().position(|other| overlap(&pattern, other)) else {
+        return 1 << pattern.iter().filter(|bit| bit.is_none()).count();
+    };
+    let (other, rest) = (&later[index], &later[index + 1..]);
+
+    // floating bit fixed by `other` splits off addresses with the opposite bit, what remains
+    // at the end matches `other` and is overwritten
+    let mut remaining = pattern;
+    let mut count = 0;
+
+    for (bit, bits) in pattern.iter().zip(other).enumerate() {
+        if let (None, Some(other_bit)) = bits {
+            let mut split = remaining;
+            split[bit] = Some(!other_bit);
+            count += count_unique(split, rest);
+            remaining[bit] = Some(*other_bit);
+        }
+    }
+
+    count
+}
+
+/// Every write in program order, addresses and values in separate vectors.
+#[derive(Default)]
+struct Memory {
+    addresses: Vec<Pattern>,
+    values:    Vec<u64>,
+}
+
+impl Memory {
+    fn write(&mut self, address: Pattern, value: u64) {
+        self.addresses.push(address);
+        self.values.push(value);
+    }
+
+    /// Each write counts only for addresses no later write overwrites.
+    fn sum(&self) -> u128 {
+        self.addresses
+            .iter()
+            .zip(&self.values)
+            .enumerate()
+            .map(|(index, (address, value))| {
+                u128::from(count_unique(*address, &self.addresses[index + 1..]))
+                    * u128::from(*value)
             })
-            .output_value();
+            .sum()
+    }
+}
 
-        Ok(sum)
+fn run(program: &[Instruction], decoder: Decoder) -> Memory {
+    let mut memory = Memory::default();
+    let (mut set, mut unset) = (0, 0);
+
+    for instruction in program {
+        match (*instruction, decoder) {
+            (Instruction::Mask(mask_set, mask_unset), _) => {
+                set = mask_set;
+                unset = mask_unset;
+            },
+            (Instruction::Mem(address, value), Decoder::Version1) => {
+                memory.write(exact(address), value & unset | set);
+            },
+            (Instruction::Mem(address, value), Decoder::Version2) => {
+                memory.write(decoded(address, set, unset), value);
+            },
+        }
     }
+
+    memory
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ex14::Ex14Error;
+    use test_case::test_case;
 
     #[test]
     fn data_from_default_file() {
         assert_eq!(Day14VariantB::solve_default_file().unwrap(), 165)
     }
+
+    #[test]
+    fn part_two_from_default_file() {
+        let input = aoc_utils::read_to_string("data_files/ex14.txt").unwrap();
+        assert_eq!(Day14VariantB::solution_part_two(&input).unwrap(), 1735166787584)
+    }
+
+    const MULTIPLE_MASKS: &str = "mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1";
+
+    #[test_case(Decoder::Version1 => 51; "version 1")]
+    #[test_case(Decoder::Version2 => 208; "version 2")]
+    fn ex14b_multiple_masks(decoder: Decoder) -> u128 {
+        run(&parse_program(MULTIPLE_MASKS).unwrap(), decoder).sum()
+    }
+
+    #[test]
+    fn ex14b_count_unique() {
+        let floating = decoded(0, 0, 0b11);
+
+        assert_eq!(count_unique(floating, &[]), 4);
+        assert_eq!(count_unique(floating, &[exact(1), exact(4)]), 3);
+        assert_eq!(count_unique(floating, &[exact(1), decoded(0, 0, 0b10)]), 1);
+    }
+
+    #[test]
+    fn ex14b_missing_mask() {
+        let err = Day14VariantB::solution("mem[8] = 11").unwrap_err();
+        assert_eq!(err.downcast::<Ex14Error>().unwrap(), Ex14Error::MissingMask.in_line(0));
+    }
 }
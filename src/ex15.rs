@@ -1,7 +1,68 @@
+use thiserror::Error;
+
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 
-pub const SIZE: usize = 2020;
+use super::diagnostic::{Diagnose, Locate};
+
+pub const PART_ONE_TURN: usize = 2020;
+pub const PART_TWO_TURN: usize = 30_000_000;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex15Error {
+    #[error("starting number {position}: invalid number `{text}`")]
+    InvalidNumber { position: usize, text: String },
+
+    #[error("no starting numbers")]
+    NoStartingNumbers,
+
+    #[error("turns are counted from 1")]
+    ZeroTurn,
+}
+
+/// Starting numbers are all in the first line.
+impl Locate for Ex15Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex15Error::NoStartingNumbers | Ex15Error::ZeroTurn => None,
+            _ => Some(1),
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex15Error::InvalidNumber { text, .. } => Some(text.trim().to_owned()),
+            Ex15Error::NoStartingNumbers | Ex15Error::ZeroTurn => None,
+        }
+    }
+}
+
+/// Comma separated starting numbers, positions in errors start from 1.
+pub fn parse_starting_numbers(s: &str) -> Result<Vec<u32>, Ex15Error> {
+    if s.trim().is_empty() {
+        return Err(Ex15Error::NoStartingNumbers);
+    }
+
+    s.trim()
+        .split(',')
+        .enumerate()
+        .map(|(index, value)| {
+            value.trim().parse::<u32>().map_err(|_| Ex15Error::InvalidNumber {
+                position: index + 1,
+                text:     value.to_owned(),
+            })
+        })
+        .collect()
+}
+
+/// Number spoken in given `turn` when it is one of the starting numbers.
+pub fn starting_number(numbers: &[u32], turn: usize) -> Result<Option<u32>, Ex15Error> {
+    if turn == 0 {
+        return Err(Ex15Error::ZeroTurn);
+    }
+
+    Ok(numbers.get(turn - 1).copied())
+}
 
 pub struct Day15VersionA;
 
@@ -15,32 +76,43 @@ impl DaySolver for Day15VersionA {
     );
 
     fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
-        let mut turns: Vec<(usize, u32)> = _s
-            .split(',')
-            .enumerate()
-            .map(|(turn_num, value)| (turn_num + 1, value.parse::<u32>().unwrap()))
-            .collect();
-
-        let (_, start_value) = turns.pop().unwrap();
-
-        let res = (turns.len() + 1..SIZE).fold(start_value, |new_num, turn_num| {
-            match turns.iter().position(|&(_, num)| num == new_num) {
-                None => {
-                    turns.push((turn_num, new_num));
-                    0
-                },
-                Some(index) => {
-                    let next_value = (turn_num - turns[index].0) as u32;
-                    turns[index] = (turn_num, new_num);
-                    next_value
-                },
-            }
-        });
-
-        Ok(res)
+        play(_s, PART_ONE_TURN)
     }
 }
 
+/// Number spoken in given `turn`, the last turn of every number is searched for in a vector.
+pub fn play(s: &str, turn: usize) -> anyhow::Result<u32> {
+    let numbers = parse_starting_numbers(s).diagnose(s)?;
+
+    if let Some(number) = starting_number(&numbers, turn)? {
+        return Ok(number);
+    }
+
+    let mut turns: Vec<(usize, u32)> = numbers
+        .into_iter()
+        .enumerate()
+        .map(|(turn_num, value)| (turn_num + 1, value))
+        .collect();
+
+    let (_, start_value) = turns.pop().ok_or(Ex15Error::NoStartingNumbers)?;
+
+    let res = (turns.len() + 1..turn).fold(start_value, |new_num, turn_num| {
+        match turns.iter().position(|&(_, num)| num == new_num) {
+            None => {
+                turns.push((turn_num, new_num));
+                0
+            },
+            Some(index) => {
+                let next_value = (turn_num - turns[index].0) as u32;
+                turns[index] = (turn_num, new_num);
+                next_value
+            },
+        }
+    });
+
+    Ok(res)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -57,6 +129,28 @@ mod tests {
         Day15VersionA::solution(s).unwrap()
     }
 
+    #[test_case("0,3,6\n" => Ok(vec![0, 3, 6]); "trailing newline")]
+    #[test_case("0,x,6" => Err(Ex15Error::InvalidNumber{position: 2, text: "x".to_owned()}); "invalid number")]
+    #[test_case("" => Err(Ex15Error::NoStartingNumbers); "empty")]
+    fn ex15_parse_starting_numbers(s: &str) -> Result<Vec<u32>, Ex15Error> {
+        parse_starting_numbers(s)
+    }
+
+    #[test_case(1 => Ok(0); "first starting number")]
+    #[test_case(3 => Ok(6); "last starting number")]
+    #[test_case(4 => Ok(0); "first spoken")]
+    #[test_case(10 => Ok(0); "tenth")]
+    #[test_case(9 => Ok(4); "ninth")]
+    fn ex15_play(turn: usize) -> Result<u32, Ex15Error> {
+        play("0,3,6", turn).map_err(|err| err.downcast().unwrap())
+    }
+
+    #[test]
+    fn ex15_play_zero_turn() {
+        let err = play("0,3,6", 0).unwrap_err();
+        assert_eq!(err.downcast::<Ex15Error>().unwrap(), Ex15Error::ZeroTurn);
+    }
+
     #[test]
     fn data_from_default_file() {
         assert_eq!(Day15VersionA::solve_default_file().unwrap(), 436)
@@ -1,4 +1,5 @@
-use super::ex15::SIZE;
+use super::diagnostic::Diagnose;
+use super::ex15::{parse_starting_numbers, starting_number, Ex15Error, PART_ONE_TURN};
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 use std::collections::HashMap;
@@ -12,34 +13,47 @@ impl DaySolver for Day15VersionB {
         .copy_with_different_variant_name("hash map");
 
     fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
-        let mut turns: HashMap<u32, usize> = HashMap::new();
-        let start_value = insert_init_values(&mut turns, _s)?;
+        play(_s, PART_ONE_TURN)
+    }
+}
 
-        let res = (turns.len() + 1..SIZE).fold(start_value, |new_num, turn_num| {
-            match turns.get(&new_num) {
-                None => {
-                    turns.insert(new_num, turn_num);
-                    0
-                },
-                Some(previous_turn) => {
-                    let next_value = (turn_num - previous_turn) as u32;
-                    turns.insert(new_num, turn_num);
-                    next_value
-                },
-            }
-        });
+/// Number spoken in given `turn`, the last turn of every number is kept in a hash map.
+pub fn play(input: &str, turn: usize) -> anyhow::Result<u32> {
+    let numbers = parse_starting_numbers(input).diagnose(input)?;
 
-        Ok(res)
+    if let Some(number) = starting_number(&numbers, turn)? {
+        return Ok(number);
     }
+
+    let mut turns: HashMap<u32, usize> = HashMap::new();
+    let start_value = insert_init_values(&mut turns, &numbers)?;
+
+    let res = (numbers.len()..turn).fold(start_value, |new_num, turn_num| {
+        match turns.get(&new_num) {
+            None => {
+                turns.insert(new_num, turn_num);
+                0
+            },
+            Some(previous_turn) => {
+                let next_value = (turn_num - previous_turn) as u32;
+                turns.insert(new_num, turn_num);
+                next_value
+            },
+        }
+    });
+
+    Ok(res)
 }
 
-fn insert_init_values(turns: &mut HashMap<u32, usize>, input: &str) -> anyhow::Result<u32> {
-    let mut last = 0;
-    for (turn_num, value) in input.split(',').enumerate() {
-        last = value.parse::<u32>()?;
-        turns.insert(last, turn_num + 1);
+/// Turns of all starting numbers but the last one, which is returned.
+fn insert_init_values(turns: &mut HashMap<u32, usize>, numbers: &[u32]) -> Result<u32, Ex15Error> {
+    let (last, numbers) = numbers.split_last().ok_or(Ex15Error::NoStartingNumbers)?;
+
+    for (turn_num, value) in numbers.iter().enumerate() {
+        turns.insert(*value, turn_num + 1);
     }
-    Ok(turns.remove_entry(&last).unwrap().0)
+
+    Ok(*last)
 }
 
 #[cfg(test)]
@@ -1,4 +1,8 @@
-use super::ex15::SIZE;
+use super::diagnostic::Diagnose;
+use super::ex15::{
+    parse_starting_numbers, starting_number, Ex15Error, PART_ONE_TURN, PART_TWO_TURN,
+};
+use super::part::PartTwo;
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 
@@ -14,37 +18,60 @@ impl DaySolver for Day15VersionC {
     );
 
     fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
-        let mut turns: Vec<usize> = vec![0; SIZE];
-        let (start_index, start_value) = insert_init_values(&mut turns, _s)?;
+        play(_s, PART_ONE_TURN)
+    }
+}
+
+impl PartTwo for Day15VersionC {
+    type OutputPartTwo = u32;
+
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+        play(s, PART_TWO_TURN)
+    }
+}
 
-        let res = (start_index..SIZE).fold(start_value, |new_num, turn_num| match turns[new_num] {
+/// Number spoken in given `turn`. Every number spoken after the starting ones is smaller than
+/// `turn`, so the last turn of each one is kept in a flat table indexed by the number, `0`
+/// stands for never spoken.
+pub fn play(input: &str, turn: usize) -> anyhow::Result<u32> {
+    let numbers = parse_starting_numbers(input).diagnose(input)?;
+
+    if let Some(number) = starting_number(&numbers, turn)? {
+        return Ok(number);
+    }
+
+    let end = u32::try_from(turn)?;
+    let largest = numbers.iter().max().map_or(0, |number| *number as usize);
+    let mut turns: Vec<u32> = vec![0; turn.max(largest + 1)];
+    let start_value = insert_init_values(&mut turns, &numbers)?;
+    let start_index = numbers.len() as u32;
+
+    let res = (start_index..end).fold(start_value, |new_num, turn_num| {
+        match turns[new_num as usize] {
             0 => {
-                turns[new_num] = turn_num;
+                turns[new_num as usize] = turn_num;
                 0
             },
 
-            turn => {
-                turns[new_num] = turn_num;
-                turn_num - turn
+            previous_turn => {
+                turns[new_num as usize] = turn_num;
+                turn_num - previous_turn
             },
-        });
+        }
+    });
 
-        Ok(res as u32)
-    }
+    Ok(res)
 }
 
-fn insert_init_values(turns: &mut [usize], input: &str) -> anyhow::Result<(usize, usize)> {
-    let mut last_value = 0;
-    let mut last_index = 0;
-    for (index, value) in input.split(',').enumerate() {
-        last_value = value.parse::<usize>()?;
-        last_index = index + 1;
-        turns[last_value] = last_index;
-    }
+/// Turns of all starting numbers but the last one, which is returned.
+fn insert_init_values(turns: &mut [u32], numbers: &[u32]) -> Result<u32, Ex15Error> {
+    let (last, numbers) = numbers.split_last().ok_or(Ex15Error::NoStartingNumbers)?;
 
-    turns[last_value] = 0;
+    for (index, value) in numbers.iter().enumerate() {
+        turns[*value as usize] = index as u32 + 1;
+    }
 
-    Ok((last_index, last_value))
+    Ok(*last)
 }
 
 #[cfg(test)]
@@ -63,8 +90,29 @@ mod tests {
         Day15VersionC::solution(s).unwrap()
     }
 
+    #[test]
+    fn ex15c_starting_number_above_turn() {
+        assert_eq!(Day15VersionC::solution("0,3000").unwrap(), 172);
+    }
+
+    #[test_case("1,1" => 1)]
+    #[test_case("0,3,0" => 43)]
+    #[test_case("2,2,2" => 32)]
+    fn ex15c_repeated_starting_number(s: &str) -> u32 {
+        let answer = Day15VersionC::solution(s).unwrap();
+        assert_eq!(answer, crate::ex15b::Day15VersionB::solution(s).unwrap());
+
+        answer
+    }
+
     #[test]
     fn data_from_default_file() {
         assert_eq!(Day15VersionC::solve_default_file().unwrap(), 436)
     }
+
+    #[test_case("0,3,6" => 175594)]
+    #[test_case("3,1,2" => 362)]
+    fn ex15c_part_two(s: &str) -> u32 {
+        Day15VersionC::solution_part_two(s).unwrap()
+    }
 }
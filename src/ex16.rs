@@ -2,6 +2,9 @@ use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 use std::ops::RangeInclusive;
 use std::str::FromStr;
+use thiserror::Error;
+
+use super::diagnostic::{Diagnose, Locate};
 
 pub struct Day16;
 
@@ -17,44 +20,108 @@ impl DaySolver for Day16 {
         DayInfo::with_day_and_file_and_variant("day_16", "data_files/ex16.txt", "base");
 
     fn solution(_s: &str) -> anyhow::Result<<Self>::Output> {
-        let (classes_section, nearby_tickets_section) = {
-            let mut sections = _s.split(INPUT_SECTION_DELIMETER);
+        let mut sections = _s.split(INPUT_SECTION_DELIMETER);
 
-            (sections.next().unwrap(), {
-                sections.next(); // skip "your ticket section"
-                sections.next().unwrap()
-            })
+        let (Some(classes_section), Some(your_ticket_section), Some(nearby_tickets_section)) =
+            (sections.next(), sections.next(), sections.next())
+        else {
+            return Err(Ex16Error::MissingSections.into());
         };
 
-        let fields_ranges = classes_section.parse::<FieldsRanges>()?;
+        let fields_ranges = classes_section.parse::<FieldsRanges>().diagnose(_s)?;
+
+        // two lines of each delimeter and the "nearby tickets:" header
+        let first_ticket_line =
+            classes_section.lines().count() + your_ticket_section.lines().count() + 4;
 
-        let result = nearby_tickets_section
-            .lines()
-            .skip(1)
-            .map(|ticket| fields_ranges.count_ticket_error_rate(ticket))
-            .sum();
+        let mut result = 0;
+
+        for (index, ticket) in nearby_tickets_section.lines().skip(1).enumerate() {
+            result += fields_ranges
+                .count_ticket_error_rate(ticket)
+                .map_err(|err| err.in_line(first_ticket_line + index))
+                .diagnose(_s)?;
+        }
 
         Ok(result)
     }
 }
 
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex16Error {
+    #[error("expected rules, your ticket and nearby tickets sections separated by empty lines")]
+    MissingSections,
+
+    #[error("invalid field rule `{0}`")]
+    InvalidRule(String),
+
+    #[error("invalid ticket value `{0}`")]
+    InvalidValue(String),
+
+    #[error("line {line}: {source}")]
+    InLine {
+        line:   usize,
+        source: Box<Ex16Error>,
+    },
+}
+
+impl Locate for Ex16Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex16Error::InLine { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex16Error::InLine { source, .. } => source.fragment(),
+            Ex16Error::InvalidRule(text) | Ex16Error::InvalidValue(text) => Some(text.to_owned()),
+            Ex16Error::MissingSections => None,
+        }
+    }
+}
+
+impl Ex16Error {
+    fn in_line(self, line: usize) -> Self {
+        Ex16Error::InLine {
+            line,
+            source: Box::new(self),
+        }
+    }
+}
+
+fn parse_range(rule: &str, range: &str) -> Result<RangeInclusive<u32>, Ex16Error> {
+    let invalid = || Ex16Error::InvalidRule(rule.to_owned());
+
+    let (start, end) = range.split_once('-').ok_or_else(invalid)?;
+
+    let start = start.parse::<u32>().map_err(|_| invalid())?;
+    let end = end.parse::<u32>().map_err(|_| invalid())?;
+
+    Ok(start..=end)
+}
+
 struct FieldsRanges {
     available_ranges: AvailableRanges,
 }
 
 impl FromStr for FieldsRanges {
-    type Err = anyhow::Error;
+    type Err = Ex16Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let available_ranges: AvailableRanges = s
-            .lines()
-            .flat_map(|line| {
-                line.split_once(": ").unwrap().1.split(" or ").map(|field| {
-                    let (start, end) = field.split_once('-').unwrap();
-                    start.parse::<u32>().unwrap()..=end.parse::<u32>().unwrap()
-                })
-            })
-            .collect();
+        let mut available_ranges: AvailableRanges = Vec::new();
+
+        for (line_num, line) in s.lines().enumerate() {
+            let (_, ranges) = line
+                .split_once(": ")
+                .ok_or_else(|| Ex16Error::InvalidRule(line.to_owned()).in_line(line_num + 1))?;
+
+            for range in ranges.split(" or ") {
+                available_ranges
+                    .push(parse_range(line, range).map_err(|err| err.in_line(line_num + 1))?);
+            }
+        }
 
         Ok(FieldsRanges { available_ranges })
     }
@@ -67,11 +134,20 @@ impl FieldsRanges {
             .any(|range| range.contains(value))
     }
 
-    fn count_ticket_error_rate(&self, line: &str) -> u32 {
-        line.split(',')
-            .map(|num| num.parse::<u32>().unwrap())
-            .filter(|value| !self.is_in_any_range(value))
-            .sum()
+    fn count_ticket_error_rate(&self, line: &str) -> Result<u32, Ex16Error> {
+        let mut error_rate = 0;
+
+        for num in line.split(',') {
+            let value = num
+                .parse::<u32>()
+                .map_err(|_| Ex16Error::InvalidValue(num.to_owned()))?;
+
+            if !self.is_in_any_range(&value) {
+                error_rate += value;
+            }
+        }
+
+        Ok(error_rate)
     }
 }
 
@@ -108,7 +184,15 @@ mod tests {
         seat: 13-40 or 45-50";
 
         let ranges = input.parse::<FieldsRanges>().unwrap();
-        ranges.count_ticket_error_rate(ticket)
+        ranges.count_ticket_error_rate(ticket).unwrap()
+    }
+
+    #[test_case("class: 1-3\n\nyour ticket:\n1\n\nnearby tickets:\n1\nx" => Ex16Error::InvalidValue("x".to_owned()).in_line(8); "invalid ticket")]
+    #[test_case("class: 1-3\nrow 6-11\n\nyour ticket:\n1\n\nnearby tickets:\n1" => Ex16Error::InvalidRule("row 6-11".to_owned()).in_line(2); "invalid rule")]
+    #[test_case("class: 1-3\nrow: 6-b\n\nyour ticket:\n1\n\nnearby tickets:\n1" => Ex16Error::InvalidRule("row: 6-b".to_owned()).in_line(2); "invalid range")]
+    #[test_case("class: 1-3\n\nyour ticket:\n1" => Ex16Error::MissingSections; "missing section")]
+    fn ex16_errors(s: &str) -> Ex16Error {
+        Day16::solution(s).unwrap_err().downcast::<Ex16Error>().unwrap()
     }
 
     #[test]
@@ -1,7 +1,9 @@
-use anyhow::*;
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 use core::str::FromStr;
+use thiserror::Error;
+
+use super::diagnostic::{Diagnose, Locate};
 
 pub struct Day17;
 
@@ -12,7 +14,7 @@ impl DaySolver for Day17 {
         DayInfo::with_day_and_file_and_variant("day_17", "data_files/ex17.txt", "base");
 
     fn solution(_s: &str) -> anyhow::Result<<Self>::Output> {
-        let start_grid = _s.parse::<Grid3D>()?;
+        let start_grid = _s.parse::<Grid3D>().diagnose(_s)?;
 
         let (_, active_cubes) = (1..=6).fold((start_grid, 0u32), |(grid, _), _| {
             let mut active_cubes = 0;
@@ -32,6 +34,52 @@ impl DaySolver for Day17 {
     }
 }
 
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex17Error {
+    #[error("unknown cube state `{0}`")]
+    UnknownCubeState(char),
+
+    #[error("expected {expected} cubes in a row, found {found}")]
+    RaggedRow { expected: usize, found: usize },
+
+    #[error("empty grid")]
+    EmptyGrid,
+
+    #[error("line {line}: {source} in `{text}`")]
+    InLine {
+        line:   usize,
+        text:   String,
+        source: Box<Ex17Error>,
+    },
+}
+
+impl Locate for Ex17Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex17Error::InLine { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex17Error::InLine { source, .. } => source.fragment(),
+            Ex17Error::UnknownCubeState(state) => Some(state.to_string()),
+            _ => None,
+        }
+    }
+}
+
+impl Ex17Error {
+    pub fn in_line(self, line_num: usize, text: &str) -> Self {
+        Ex17Error::InLine {
+            line:   line_num + 1,
+            text:   text.to_owned(),
+            source: Box::new(self),
+        }
+    }
+}
+
 type GridBody = Vec<Vec<Vec<CubeState>>>;
 type Grid2DBody = Vec<Vec<CubeState>>;
 
@@ -44,20 +92,37 @@ struct Grid3D {
 }
 
 impl FromStr for Grid3D {
-    type Err = anyhow::Error;
+    type Err = Ex17Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let base_body: Grid2DBody = s
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|field| CubeState::try_from(field).unwrap())
-                    .collect()
-            })
-            .collect();
+        let mut base_body: Grid2DBody = Vec::new();
+
+        for (line_num, line) in s.lines().enumerate() {
+            let row = line
+                .chars()
+                .map(CubeState::try_from)
+                .collect::<Result<Vec<CubeState>, Ex17Error>>()
+                .map_err(|err| err.in_line(line_num, line))?;
 
-        let columns = base_body.len();
-        let rows = base_body[0].len();
+            if let Some(first_row) = base_body.first() {
+                if first_row.len() != row.len() {
+                    let err = Ex17Error::RaggedRow {
+                        expected: first_row.len(),
+                        found:    row.len(),
+                    };
+                    return Err(err.in_line(line_num, line));
+                }
+            }
+
+            base_body.push(row);
+        }
+
+        if base_body.is_empty() || base_body[0].is_empty() {
+            return Err(Ex17Error::EmptyGrid);
+        }
+
+        let columns = base_body[0].len();
+        let rows = base_body.len();
         let depth = 1;
 
         Ok(Grid3D {
@@ -173,7 +238,7 @@ enum CubeState {
 }
 
 impl TryFrom<char> for CubeState {
-    type Error = anyhow::Error;
+    type Error = Ex17Error;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         if value == '#' {
@@ -181,7 +246,7 @@ impl TryFrom<char> for CubeState {
         } else if value == '.' {
             Ok(CubeState::Inactive)
         } else {
-            Err(anyhow!("Unknown cube state: {}", value))
+            Err(Ex17Error::UnknownCubeState(value))
         }
     }
 }
@@ -189,7 +254,7 @@ impl TryFrom<char> for CubeState {
 #[cfg(test)]
 mod tests {
     use super::*;
-    //use test_case::test_case;
+    use test_case::test_case;
 
     #[test]
     fn ex17_data_from_default_file() {
@@ -215,10 +280,40 @@ mod tests {
         assert_eq!(result.depth, 1);
     }
 
+    #[test]
+    fn ex17_grid3d_from_str_not_square() {
+        let result = ".#..\n..##".parse::<Grid3D>().unwrap();
+
+        assert_eq!((result.columns, result.rows, result.depth), (4, 2, 1));
+    }
+
+    #[test_case(".#..\n..##" => 68; "wider than high")]
+    #[test_case("#\n#\n#\n." => 112; "higher than wide")]
+    fn ex17_not_square_grid(s: &str) -> u32 {
+        Day17::solution(s).unwrap()
+    }
+
     #[test]
     fn ex17_cube_state_from() {
         assert_eq!(CubeState::try_from('#').unwrap(), CubeState::Active);
         assert_eq!(CubeState::try_from('.').unwrap(), CubeState::Inactive);
         assert!(CubeState::try_from('-').is_err());
     }
+
+    #[test]
+    fn ex17_grid3d_from_str_errors() {
+        assert_eq!(
+            ".#.\n.-#".parse::<Grid3D>(),
+            Err(Ex17Error::UnknownCubeState('-').in_line(1, ".-#"))
+        );
+        assert_eq!(
+            ".#.\n.#".parse::<Grid3D>(),
+            Err(Ex17Error::RaggedRow {
+                expected: 3,
+                found:    2,
+            }
+            .in_line(1, ".#"))
+        );
+        assert_eq!("".parse::<Grid3D>(), Err(Ex17Error::EmptyGrid));
+    }
 }
\ No newline at end of file
@@ -2,16 +2,20 @@ use aoc_utils::{DayInfo, DaySolver};
 use fnv::FnvHashMap as HashMap;
 use fnv::FnvHashSet as HashSet;
 
+use super::diagnostic::Diagnose;
+use super::ex17::Ex17Error;
+
 // -----------------------------------------------------------------------------
 pub struct Day17other;
 
 impl DaySolver for Day17other {
     type Output = u32;
 
-    const INFO: DayInfo = DayInfo::with_day_and_file("day_17", "data_files/ex17.txt");
+    const INFO: DayInfo =
+        DayInfo::with_day_and_file_and_variant("day_17", "data_files/ex17.txt", "hash set");
 
     fn solution(s: &str) -> anyhow::Result<Self::Output> {
-        let mut grid = Grid::initial(s);
+        let mut grid = Grid::initial(s).diagnose(s)?;
 
         grid.cycle_n_times(6);
 
@@ -45,23 +49,22 @@ impl Point3D {
 // -----------------------------------------------------------------------------
 
 impl Grid {
-    fn initial(s: &str) -> Self {
-        fn is_active(b: u8) -> bool {
-            b == b'#'
-        }
-
+    fn initial(s: &str) -> Result<Self, Ex17Error> {
         let mut data: HashSet<Point3D> = HashSet::default();
 
-        for (row, cells_states_as_bytes) in s.lines().enumerate() {
-            for (col, byte) in cells_states_as_bytes.bytes().enumerate() {
-                if is_active(byte) {
-                    let point = Point3D::new(col as i32, row as i32, 0);
-                    data.insert(point);
+        for (row, cells_states) in s.lines().enumerate() {
+            for (col, state) in cells_states.chars().enumerate() {
+                match state {
+                    '#' => {
+                        data.insert(Point3D::new(col as i32, row as i32, 0));
+                    },
+                    '.' => {},
+                    _ => return Err(Ex17Error::UnknownCubeState(state).in_line(row, cells_states)),
                 }
             }
         }
 
-        Self { data }
+        Ok(Self { data })
     }
 
     fn cycle_n_times(&mut self, n: u32) {
@@ -1,33 +1,80 @@
-use anyhow::Ok;
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 use std::str::FromStr;
+use thiserror::Error;
+
+use super::diagnostic::{Diagnose, Locate};
 
 pub struct Day18;
 
 impl DaySolver for Day18 {
     type Output = u32;
 
-    const INFO: DayInfo = DayInfo::with_day_and_file_and_variant(
-        "day_18",
-        "data_files/ex18.txt",
-        "vector with tuple",
-    );
+    const INFO: DayInfo =
+        DayInfo::with_day_and_file_and_variant("day_18", "data_files/ex18.txt", "base");
 
     fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
-        let expresions = _s.replace('(', "( ").replace(')', " )");
-
-        let i: u32 = expresions
-            .lines()
-            .map(|line| line.parse::<Evaluator>())
-            .filter(|evaluator| (*evaluator).is_ok())
-            .map(|evaluator| evaluator.unwrap().evaluate())
-            .sum();
+        let mut i: u32 = 0;
+
+        for (line_num, line) in _s.lines().enumerate() {
+            let value = line
+                .replace('(', "( ")
+                .replace(')', " )")
+                .parse::<Evaluator>()
+                .and_then(|evaluator| evaluator.evaluate())
+                .map_err(|err| Ex18Error::InLine {
+                    line:   line_num + 1,
+                    text:   line.to_owned(),
+                    source: Box::new(err),
+                })
+                .diagnose(_s)?;
+
+            i += value;
+        }
 
         Ok(i)
     }
 }
 
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex18Error {
+    #[error("unknown token `{0}`")]
+    UnknownToken(String),
+
+    #[error("unbalanced brackets")]
+    UnbalancedBrackets,
+
+    #[error("operator without two operands")]
+    MissingOperand,
+
+    #[error("operands without operator")]
+    MissingOperator,
+
+    #[error("line {line}: {source} in `{text}`")]
+    InLine {
+        line:   usize,
+        text:   String,
+        source: Box<Ex18Error>,
+    },
+}
+
+impl Locate for Ex18Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex18Error::InLine { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex18Error::InLine { source, .. } => source.fragment(),
+            Ex18Error::UnknownToken(token) => Some(token.to_owned()),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 enum Component {
     Num(u32),
@@ -40,16 +87,19 @@ fn is_operator(element: &str) -> bool {
     element == "*" || element == "+"
 }
 
-fn get_operator(element: &str) -> Component {
+fn get_operator(element: &str) -> Result<Component, Ex18Error> {
     match element {
-        "*" => Component::Multiply,
-        "+" => Component::Sum,
-        _ => unreachable!(),
+        "*" => Ok(Component::Multiply),
+        "+" => Ok(Component::Sum),
+        _ => Err(Ex18Error::UnknownToken(element.to_owned())),
     }
 }
 
-fn pop_two(vector: &mut Vec<u32>) -> (u32, u32) {
-    (vector.pop().unwrap(), vector.pop().unwrap())
+fn pop_two(vector: &mut Vec<u32>) -> Result<(u32, u32), Ex18Error> {
+    match (vector.pop(), vector.pop()) {
+        (Some(a), Some(b)) => Ok((a, b)),
+        _ => Err(Ex18Error::MissingOperand),
+    }
 }
 
 struct Evaluator {
@@ -57,7 +107,7 @@ struct Evaluator {
 }
 
 impl FromStr for Evaluator {
-    type Err = anyhow::Error;
+    type Err = Ex18Error;
 
     // basic implementation of infix to postfix converter
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -74,8 +124,8 @@ impl FromStr for Evaluator {
                     }
                 }
 
-                if !stack.is_empty() && stack[stack.len() - 1] == Component::LeftBracket {
-                    let _t = stack.pop();
+                if stack.pop() != Some(Component::LeftBracket) {
+                    return Err(Ex18Error::UnbalancedBrackets);
                 }
             } else if is_operator(ch) {
                 while !stack.is_empty()
@@ -87,18 +137,22 @@ impl FromStr for Evaluator {
                     }
                 }
 
-                stack.push(get_operator(ch))
+                stack.push(get_operator(ch)?)
             } else {
                 // case when ch is number
-                let t = Component::Num((ch).parse::<u32>()?);
-                postfix.push(t);
+                let num = ch
+                    .parse::<u32>()
+                    .map_err(|_| Ex18Error::UnknownToken(ch.to_owned()))?;
+                postfix.push(Component::Num(num));
             }
         }
 
-        while !stack.is_empty() {
-            if let Some(element) = stack.pop() {
-                postfix.push(element);
+        while let Some(element) = stack.pop() {
+            if element == Component::LeftBracket {
+                return Err(Ex18Error::UnbalancedBrackets);
             }
+
+            postfix.push(element);
         }
 
         Ok(Evaluator { postfix })
@@ -106,39 +160,44 @@ impl FromStr for Evaluator {
 }
 
 impl Evaluator {
-    fn evaluate(&self) -> u32 {
+    fn evaluate(&self) -> Result<u32, Ex18Error> {
         use super::ex18::Component::*;
 
         let res = self
             .postfix
             .iter()
-            .fold(Vec::new(), |mut stack, element| match *element {
+            .try_fold(Vec::new(), |mut stack, element| match *element {
                 Num(number) => {
                     stack.push(number);
 
-                    stack
+                    Ok(stack)
                 },
 
                 Sum => {
-                    let (a, b) = pop_two(&mut stack);
+                    let (a, b) = pop_two(&mut stack)?;
 
                     stack.push(a + b);
 
-                    stack
+                    Ok(stack)
                 },
 
                 Multiply => {
-                    let (a, b) = pop_two(&mut stack);
+                    let (a, b) = pop_two(&mut stack)?;
 
                     stack.push(a * b);
 
-                    stack
+                    Ok(stack)
                 },
 
-                _ => unreachable!(),
-            });
+                // brackets never get to the postfix form
+                LeftBracket => Err(Ex18Error::UnbalancedBrackets),
+            })?;
 
-        res[0]
+        match res[..] {
+            [value] => Ok(value),
+            [] => Err(Ex18Error::MissingOperand),
+            _ => Err(Ex18Error::MissingOperator),
+        }
     }
 }
 
@@ -158,7 +217,17 @@ mod tests {
     #[test_case("5 + ( 8 * 3 + 9 + 3 * 4 * 3 )" => 437)]
     #[test_case("( ( 2 + 4 * 9 ) * ( 6 + 9 * 8 + 6 ) + 6 ) + 2 + 4 * 2" => 13632)]
     fn ex18_evaluate(s: &str) -> u32 {
-        s.parse::<Evaluator>().unwrap().evaluate()
+        s.parse::<Evaluator>().unwrap().evaluate().unwrap()
+    }
+
+    #[test_case("2 * ( 3 + 4" => Err(Ex18Error::UnbalancedBrackets); "missing closing bracket")]
+    #[test_case("2 * 3 + 4 )" => Err(Ex18Error::UnbalancedBrackets); "missing opening bracket")]
+    #[test_case("2 * 3 -" => Err(Ex18Error::UnknownToken("-".to_owned())); "unknown operator")]
+    #[test_case("2 *" => Err(Ex18Error::MissingOperand); "missing operand")]
+    #[test_case("2 3" => Err(Ex18Error::MissingOperator); "missing operator")]
+    #[test_case("" => Err(Ex18Error::MissingOperand); "empty")]
+    fn ex18_errors(s: &str) -> Result<u32, Ex18Error> {
+        s.parse::<Evaluator>()?.evaluate()
     }
 
     #[test]
@@ -3,6 +3,9 @@ use aoc_utils::DaySolver;
 use fnv::FnvHashMap as HashMap;
 use std::fmt::Debug;
 use std::str::FromStr;
+use thiserror::Error;
+
+use super::diagnostic::{Diagnose, Locate};
 
 type RuleNum = usize;
 type Letter = char;
@@ -18,9 +21,9 @@ impl DaySolver for Day19 {
         DayInfo::with_day_and_file_and_variant("day_19", "data_files/ex19.txt", "base");
 
     fn solution(_s: &str) -> anyhow::Result<<Self>::Output> {
-        let (rules, messages) = _s.split_once("\n\n").unwrap();
+        let (rules, messages) = _s.split_once("\n\n").ok_or(Ex19Error::MissingMessages)?;
 
-        let tree = Tree::try_from(rules.parse::<Rules>()?)?;
+        let tree = Tree::try_from(rules.parse::<Rules>().diagnose(_s)?)?;
 
         let result: usize = messages
             .lines()
@@ -31,6 +34,60 @@ impl DaySolver for Day19 {
     }
 }
 
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex19Error {
+    #[error("rules and messages have to be separated by an empty line")]
+    MissingMessages,
+
+    #[error("expected `<number>: <rule>`, found `{0}`")]
+    MissingColon(String),
+
+    #[error("invalid rule number `{0}`")]
+    InvalidRuleNumber(String),
+
+    #[error("invalid rule `{0}`")]
+    InvalidRule(String),
+
+    #[error("rule {0} is referenced but not defined")]
+    UnknownRule(RuleNum),
+
+    #[error("line {line}: {source}")]
+    InLine {
+        line:   usize,
+        source: Box<Ex19Error>,
+    },
+}
+
+impl Locate for Ex19Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex19Error::InLine { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex19Error::InLine { source, .. } => source.fragment(),
+            Ex19Error::MissingColon(text)
+            | Ex19Error::InvalidRuleNumber(text)
+            | Ex19Error::InvalidRule(text) => Some(text.to_owned()),
+            _ => None,
+        }
+    }
+}
+
+fn parse_rule_numbers(s: &str) -> Result<SubRuleType, Ex19Error> {
+    s.trim()
+        .split(' ')
+        .map(|rule_num| {
+            rule_num
+                .parse::<RuleNum>()
+                .map_err(|_| Ex19Error::InvalidRuleNumber(rule_num.to_owned()))
+        })
+        .collect()
+}
+
 #[derive(Debug, PartialEq)]
 enum Rule {
     Letter(Letter),
@@ -39,7 +96,7 @@ enum Rule {
 }
 
 impl FromStr for Rule {
-    type Err = anyhow::Error;
+    type Err = Ex19Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         use Rule::*;
@@ -48,24 +105,18 @@ impl FromStr for Rule {
             let body: SubRulesAlternative = s
                 .trim()
                 .split('|')
-                .map(|sub_rules| {
-                    sub_rules
-                        .trim()
-                        .split(' ')
-                        .map(|rule_num| rule_num.parse::<usize>().unwrap())
-                        .collect()
-                })
-                .collect();
+                .map(parse_rule_numbers)
+                .collect::<Result<SubRulesAlternative, Ex19Error>>()?;
             Ok(AlternativeSubRules(body))
         } else if s.contains('"') {
-            Ok(Letter(s.trim().replace('"', "").parse::<char>()?))
-        } else {
-            let body: SubRuleType = s
+            let letter = s
                 .trim()
-                .split(' ')
-                .map(|rule_num| rule_num.parse::<usize>().unwrap())
-                .collect();
-            Ok(SubRules(body))
+                .replace('"', "")
+                .parse::<char>()
+                .map_err(|_| Ex19Error::InvalidRule(s.trim().to_owned()))?;
+            Ok(Letter(letter))
+        } else {
+            Ok(SubRules(parse_rule_numbers(s)?))
         }
     }
 }
@@ -76,28 +127,41 @@ struct Rules {
 }
 
 impl FromStr for Rules {
-    type Err = anyhow::Error;
+    type Err = Ex19Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let body: HashMap<RuleNum, Rule> = s
-            .lines()
-            .map(|line| {
-                let (num, rule_body) = line.split_once(':').unwrap();
+        let mut body: HashMap<RuleNum, Rule> = HashMap::default();
+
+        for (line_num, line) in s.lines().enumerate() {
+            let parse_line = || -> Result<(RuleNum, Rule), Ex19Error> {
+                let (num, rule_body) = line
+                    .split_once(':')
+                    .ok_or_else(|| Ex19Error::MissingColon(line.to_owned()))?;
+
+                let num = num
+                    .parse::<RuleNum>()
+                    .map_err(|_| Ex19Error::InvalidRuleNumber(num.to_owned()))?;
+
+                Ok((num, rule_body.parse::<Rule>()?))
+            };
 
-                (
-                    num.parse::<usize>().unwrap(),
-                    rule_body.parse::<Rule>().unwrap(),
-                )
-            })
-            .collect();
+            let (num, rule) = parse_line().map_err(|err| Ex19Error::InLine {
+                line:   line_num + 1,
+                source: Box::new(err),
+            })?;
+
+            body.insert(num, rule);
+        }
 
         Ok(Rules { body })
     }
 }
 
 impl Rules {
-    fn get(&self, rule_num: &RuleNum) -> &Rule {
-        self.body.get(rule_num).unwrap()
+    fn get(&self, rule_num: &RuleNum) -> Result<&Rule, Ex19Error> {
+        self.body
+            .get(rule_num)
+            .ok_or(Ex19Error::UnknownRule(*rule_num))
     }
 }
 
@@ -113,25 +177,25 @@ impl Tree {
         start_node_num: usize,
         rule_number: &RuleNum,
         rules: &Rules,
-    ) -> Option<usize> {
+    ) -> Result<Option<usize>, Ex19Error> {
         use Rule::*;
-        match rules.get(rule_number) {
+        match rules.get(rule_number)? {
             Letter(body) => {
                 self.nodes.push(Node {
                     value: Some(*body),
                     children: vec![],
                 });
-                Some(self.get_last_index())
+                Ok(Some(self.get_last_index()))
             },
 
             SubRules(rules_sequence) => {
                 for rule in rules_sequence {
-                    if let Some(node_to_add) = self.helper(start_node_num, rule, rules) {
+                    if let Some(node_to_add) = self.helper(start_node_num, rule, rules)? {
                         self.connect_node_to_node_leaves(start_node_num, node_to_add);
                     }
                 }
 
-                None
+                Ok(None)
             },
 
             AlternativeSubRules(rules_sequence_collection) => {
@@ -147,13 +211,13 @@ impl Tree {
                     self.connect_node_to_nodes(&leaves, &empty_node_index);
 
                     for rule in rules_sequence {
-                        if let Some(node_to_add) = self.helper(empty_node_index, rule, rules) {
+                        if let Some(node_to_add) = self.helper(empty_node_index, rule, rules)? {
                             self.connect_node_to_node_leaves(empty_node_index, node_to_add);
                         }
                     }
                 }
 
-                None
+                Ok(None)
             },
         }
     }
@@ -204,6 +268,10 @@ impl Tree {
     fn does_message_match(&self, message: &str) -> bool {
         let message_vec: Vec<char> = message.chars().collect();
 
+        if message_vec.is_empty() {
+            return false;
+        }
+
         self.recur_match(&self.root_index, &message_vec, 0)
     }
 
@@ -232,12 +300,12 @@ impl Tree {
 }
 
 impl TryFrom<Rules> for Tree {
-    type Error = anyhow::Error;
+    type Error = Ex19Error;
 
     fn try_from(rules: Rules) -> Result<Self, Self::Error> {
         let mut searching_tree = Tree::new(None);
 
-        searching_tree.helper(0, &0, &rules);
+        searching_tree.helper(0, &0, &rules)?;
 
         Ok(searching_tree)
     }
@@ -299,12 +367,28 @@ mod tests {
         let input = "0: 1 2\n1: \"a\"\n2: 1 3 | 3 1\n3: \"b\"";
         let rules = input.parse::<Rules>().unwrap();
 
-        assert_eq!(*rules.get(&0), SubRules(vec![1, 2]));
-        assert_eq!(*rules.get(&1), Letter('a'));
+        assert_eq!(*rules.get(&0).unwrap(), SubRules(vec![1, 2]));
+        assert_eq!(*rules.get(&1).unwrap(), Letter('a'));
         assert_eq!(
-            *rules.get(&2),
+            *rules.get(&2).unwrap(),
             AlternativeSubRules(vec![vec![1, 3], vec![3, 1]])
-        )
+        );
+        assert_eq!(rules.get(&4), Err(Ex19Error::UnknownRule(4)));
+    }
+
+    #[test_case("0: 1 2\n1 \"a\"" => Ex19Error::InLine{line: 2, source: Box::new(Ex19Error::MissingColon("1 \"a\"".to_owned()))}; "missing colon")]
+    #[test_case("0: 1 x" => Ex19Error::InLine{line: 1, source: Box::new(Ex19Error::InvalidRuleNumber("x".to_owned()))}; "invalid sub rule")]
+    #[test_case("0: \"ab\"" => Ex19Error::InLine{line: 1, source: Box::new(Ex19Error::InvalidRule("\"ab\"".to_owned()))}; "invalid letter")]
+    fn ex19_rules_fromstr_errors(input: &str) -> Ex19Error {
+        input.parse::<Rules>().unwrap_err()
+    }
+
+    #[test]
+    fn ex19_tree_tryfrom_unknown_rule() {
+        let input = "0: 1 2\n1: \"a\"";
+        let tree = Tree::try_from(input.parse::<Rules>().unwrap());
+
+        assert_eq!(tree, Err(Ex19Error::UnknownRule(2)));
     }
 
     #[test_case(Node { value: None, children: vec![] } => true)]
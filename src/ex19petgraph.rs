@@ -8,6 +8,9 @@ use petgraph::Direction;
 use fnv::FnvHashMap as HashMap;
 use std::str::FromStr;
 
+use super::diagnostic::Diagnose;
+use super::ex19::Ex19Error;
+
 type RuleNum = usize;
 type Letter = char;
 type RulesSequenceType = Vec<RuleNum>;
@@ -18,16 +21,13 @@ pub struct Day19petgraph;
 impl DaySolver for Day19petgraph {
     type Output = usize;
 
-    const INFO: DayInfo = DayInfo::with_day_and_file_and_variant(
-        "day_19_petgraph",
-        "data_files/ex19.txt",
-        "petgraph",
-    );
+    const INFO: DayInfo =
+        DayInfo::with_day_and_file_and_variant("day_19", "data_files/ex19.txt", "petgraph");
 
     fn solution(_s: &str) -> anyhow::Result<<Self>::Output> {
-        let (rules, messages) = _s.split_once("\n\n").unwrap();
+        let (rules, messages) = _s.split_once("\n\n").ok_or(Ex19Error::MissingMessages)?;
 
-        let tree = Tree::from_rules(get_rules(rules));
+        let tree = Tree::from_rules(get_rules(rules).diagnose(_s)?)?;
 
         let res = messages
             .lines()
@@ -46,42 +46,65 @@ enum Rule {
 }
 
 impl FromStr for Rule {
-    type Err = anyhow::Error;
+    type Err = Ex19Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         use Rule::*;
 
         match s.split_once('|') {
             Some((first, second)) => Ok(RulesSequenceAlternative(
-                get_rules_sequence(first),
-                get_rules_sequence(second),
+                get_rules_sequence(first)?,
+                get_rules_sequence(second)?,
             )),
             None => {
                 if s.contains('"') {
-                    Ok(Letter(s.trim().chars().nth(1).unwrap())) // s.chars -> '"', 'a', '"'
+                    // s.chars -> '"', 'a', '"'
+                    let letter = s
+                        .trim()
+                        .chars()
+                        .nth(1)
+                        .ok_or_else(|| Ex19Error::InvalidRule(s.trim().to_owned()))?;
+                    Ok(Letter(letter))
                 } else {
-                    Ok(RulesSequence(get_rules_sequence(s)))
+                    Ok(RulesSequence(get_rules_sequence(s)?))
                 }
             },
         }
     }
 }
 
-fn get_rules_sequence(s: &str) -> Vec<RuleNum> {
+fn get_rules_sequence(s: &str) -> Result<Vec<RuleNum>, Ex19Error> {
     s.split(' ')
         .filter(|char| !char.is_empty())
-        .map(|rule_num| rule_num.parse::<RuleNum>().unwrap())
+        .map(|rule_num| {
+            rule_num
+                .parse::<RuleNum>()
+                .map_err(|_| Ex19Error::InvalidRuleNumber(rule_num.to_owned()))
+        })
         .collect()
 }
 
-fn get_rules(s: &str) -> Rules {
+fn get_rule(line: &str) -> Result<(RuleNum, Rule), Ex19Error> {
+    let (num, rule_body) = line
+        .trim()
+        .split_once(':')
+        .ok_or_else(|| Ex19Error::MissingColon(line.trim().to_owned()))?;
+
+    let num = num
+        .parse::<RuleNum>()
+        .map_err(|_| Ex19Error::InvalidRuleNumber(num.to_owned()))?;
+
+    Ok((num, rule_body.parse::<Rule>()?))
+}
+
+fn get_rules(s: &str) -> Result<Rules, Ex19Error> {
     s.lines()
-        .map(|line| {
-            let (num, rule_body) = line.trim().split_once(':').unwrap();
-            (
-                num.parse::<usize>().unwrap(),
-                rule_body.parse::<Rule>().unwrap(),
-            )
+        .enumerate()
+        .map(|(line_num, line)| {
+            get_rule(line).map_err(|err| Ex19Error::InLine {
+                line:   line_num + 1,
+                source: Box::new(err),
+            })
         })
         .collect()
 }
@@ -92,15 +115,15 @@ struct Tree {
 }
 
 impl Tree {
-    fn from_rules(rules: Rules) -> Self {
+    fn from_rules(rules: Rules) -> Result<Self, Ex19Error> {
         let mut body: Graph<Letter, ()> = Graph::new();
         let root_index = body.add_node(' ');
 
         let mut tree = Tree { root_index, body };
 
-        let _res = tree.helper(NodeIndex::new(0), &0, &rules);
+        tree.helper(NodeIndex::new(0), &0, &rules)?;
 
-        tree
+        Ok(tree)
     }
 
     fn helper(
@@ -108,16 +131,20 @@ impl Tree {
         start_node_num: NodeIndex,
         rule_number: &RuleNum,
         rules: &Rules,
-    ) -> Option<NodeIndex> {
+    ) -> Result<Option<NodeIndex>, Ex19Error> {
         use Rule::*;
 
-        match rules.get(rule_number).unwrap() {
-            Letter(letter) => Some(self.body.add_node(*letter)),
+        let rule = rules
+            .get(rule_number)
+            .ok_or(Ex19Error::UnknownRule(*rule_number))?;
+
+        match rule {
+            Letter(letter) => Ok(Some(self.body.add_node(*letter))),
 
             RulesSequence(sequence) => {
-                self.rules_sequence_walk(start_node_num, rules, sequence);
+                self.rules_sequence_walk(start_node_num, rules, sequence)?;
 
-                None
+                Ok(None)
             },
 
             RulesSequenceAlternative(sequence_left, sequence_right) => {
@@ -127,10 +154,10 @@ impl Tree {
                     let empty_node = self.body.add_node(' ');
 
                     self.connect_node_to_nodes(&leaves, &empty_node);
-                    self.rules_sequence_walk(empty_node, rules, sequence);
+                    self.rules_sequence_walk(empty_node, rules, sequence)?;
                 }
 
-                None
+                Ok(None)
             },
         }
     }
@@ -144,6 +171,10 @@ impl Tree {
     fn does_message_match(&self, message: &str) -> bool {
         let message_vec: Vec<char> = message.chars().collect();
 
+        if message_vec.is_empty() {
+            return false;
+        }
+
         self.recur_match(&self.root_index, &message_vec, 0)
     }
 
@@ -177,12 +208,14 @@ impl Tree {
         start_node_num: NodeIndex,
         rules: &Rules,
         sequence: &Vec<usize>,
-    ) {
+    ) -> Result<(), Ex19Error> {
         for rule in sequence {
-            if let Some(node_to_connect) = self.helper(start_node_num, rule, rules) {
+            if let Some(node_to_connect) = self.helper(start_node_num, rule, rules)? {
                 self.connect_node_to_node_leaves(start_node_num, node_to_connect);
             }
         }
+
+        Ok(())
     }
 
     fn connect_node_to_node_leaves(&mut self, parrent_node: NodeIndex, node_to_connect: NodeIndex) {
@@ -239,7 +272,7 @@ mod tests {
         rules.insert(2, RulesSequenceAlternative(vec![1, 3], vec![3, 1]));
         rules.insert(3, Letter('b'));
 
-        assert_eq!(get_rules(input), rules)
+        assert_eq!(get_rules(input), Ok(rules))
     }
 
     #[test]
@@ -247,6 +280,22 @@ mod tests {
         assert_eq!(Day19petgraph::solve_default_file().unwrap(), 2)
     }
 
+    #[test_case("0: 1 2\n1 \"a\"" => Ex19Error::InLine{line: 2, source: Box::new(Ex19Error::MissingColon("1 \"a\"".to_owned()))}; "missing colon")]
+    #[test_case("0: 1 | 2 | 3" => Ex19Error::InLine{line: 1, source: Box::new(Ex19Error::InvalidRuleNumber("|".to_owned()))}; "more than two alternatives")]
+    fn ex19_get_rules_errors(input: &str) -> Ex19Error {
+        get_rules(input).unwrap_err()
+    }
+
+    #[test]
+    fn ex19_tree_from_rules_unknown_rule() {
+        let rules = get_rules("0: 1 2\n1: \"a\"").unwrap();
+
+        assert!(matches!(
+            Tree::from_rules(rules),
+            Err(Ex19Error::UnknownRule(2))
+        ));
+    }
+
     #[test]
     fn ex19_tree_from_rules() {
         let input = r#"0: 1 2
@@ -254,7 +303,7 @@ mod tests {
         2: 1 3 | 3 1
         3: "b""#;
 
-        let tree = Tree::from_rules(get_rules(input));
+        let tree = Tree::from_rules(get_rules(input).unwrap()).unwrap();
 
         let mut body: Graph<char, ()> = Graph::new();
 
@@ -291,7 +340,7 @@ mod tests {
         2: 1 3 | 3 1
         3: "b""#;
 
-        let tree = Tree::from_rules(get_rules(input));
+        let tree = Tree::from_rules(get_rules(input).unwrap()).unwrap();
 
         assert_eq!(
             tree.get_node_children(&NodeIndex::new(1)),
@@ -312,7 +361,7 @@ mod tests {
         2: 1 3 | 3 1
         3: "b""#;
 
-        let tree = Tree::from_rules(get_rules(input));
+        let tree = Tree::from_rules(get_rules(input).unwrap()).unwrap();
 
         tree.does_message_match(message)
     }
@@ -326,7 +375,7 @@ mod tests {
         2: 1 3 | 3 1
         3: "b""#;
 
-        let tree = Tree::from_rules(get_rules(input));
+        let tree = Tree::from_rules(get_rules(input).unwrap()).unwrap();
 
         tree.get_node_leaves(node, &mut Vec::new())
     }
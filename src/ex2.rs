@@ -1,10 +1,14 @@
 use lazy_regex::{regex, Lazy, Regex};
+use std::fmt;
 use std::str::FromStr;
 use thiserror::Error;
 
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 
+use super::diagnostic::{Diagnose, Locate};
+use super::part::PartTwo;
+
 pub struct Day2;
 
 impl DaySolver for Day2 {
@@ -13,39 +17,211 @@ impl DaySolver for Day2 {
     const INFO: DayInfo = DayInfo::with_day_and_file("day_2", "data_files/ex2.txt");
 
     fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
+        let passwords = parse_passwords(_s)?;
+        Ok(count_valid(&passwords, &CountInRange))
+    }
+}
 
-        let res = _s.lines().filter_map(|line| line.parse::<Password>().ok()).count();
+impl PartTwo for Day2 {
+    type OutputPartTwo = usize;
+
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+        let passwords = parse_passwords(s)?;
+        Ok(count_valid(&passwords, &ExactlyOnePosition))
+    }
+}
 
-        Ok(res)
+impl Day2 {
+    /// Valid counts and failing lines of every policy in `policies`.
+    pub fn report(s: &str, policies: &Policies) -> anyhow::Result<Vec<PolicyReport>> {
+        let passwords = parse_passwords(s)?;
+        Ok(policies.report(&passwords))
     }
 }
 
+pub fn parse_passwords(s: &str) -> anyhow::Result<Vec<Password>> {
+    s.lines()
+        .enumerate()
+        .map(|(line_num, line)| {
+            line.parse::<Password>()
+                .map_err(|err| PasswordError::InLine {
+                    line:   line_num + 1,
+                    source: Box::new(err),
+                })
+        })
+        .collect::<Result<Vec<Password>, PasswordError>>()
+        .diagnose(s)
+}
+
+fn count_valid(passwords: &[Password], policy: &dyn PasswordPolicy) -> usize {
+    passwords
+        .iter()
+        .filter(|password| policy.is_valid(password))
+        .count()
+}
+
 #[derive(PartialEq, Debug, Eq)]
 pub struct Password {
-    min_number:   u16,
-    max_number:   u16,
-    checked_char: char,
-    passwd:       String,
+    pub min_number:   u16,
+    pub max_number:   u16,
+    pub checked_char: char,
+    pub passwd:       String,
 }
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum PasswordError {
-    #[error("unable to capture password")]
-    CaptureFailed,
+    #[error("unable to capture password from `{0}`")]
+    CaptureFailed(String),
+
+    #[error("number out of range in `{0}`")]
+    InvalidNumber(String),
+
+    #[error("line {line}: {source}")]
+    InLine {
+        line:   usize,
+        source: Box<PasswordError>,
+    },
+}
+
+impl Locate for PasswordError {
+    fn line(&self) -> Option<usize> {
+        match self {
+            PasswordError::InLine { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            PasswordError::InLine { source, .. } => source.fragment(),
+            _ => None,
+        }
+    }
 }
 
 impl Password {
     pub fn is_valid(&self) -> bool {
-        let counter: u16 = self
+        CountInRange.is_valid(self)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// Rule deciding if a password was valid under the policy of the shop.
+pub trait PasswordPolicy {
+    fn name(&self) -> &str;
+
+    fn is_valid(&self, password: &Password) -> bool;
+}
+
+/// Checked char occurs between the two numbers of times, part one.
+pub struct CountInRange;
+
+/// Checked char is at exactly one of the two positions counted from 1, part two.
+pub struct ExactlyOnePosition;
+
+impl PasswordPolicy for CountInRange {
+    fn name(&self) -> &str {
+        "count in range"
+    }
+
+    fn is_valid(&self, password: &Password) -> bool {
+        let counter = password
             .passwd
             .chars()
-            .filter(|c| *c == self.checked_char)
-            .count() as u16;
+            .filter(|c| *c == password.checked_char)
+            .count();
+
+        (usize::from(password.min_number)..=usize::from(password.max_number)).contains(&counter)
+    }
+}
+
+impl PasswordPolicy for ExactlyOnePosition {
+    fn name(&self) -> &str {
+        "exactly one position"
+    }
+
+    fn is_valid(&self, password: &Password) -> bool {
+        let at = |position: u16| {
+            usize::from(position)
+                .checked_sub(1)
+                .and_then(|index| password.passwd.chars().nth(index))
+                == Some(password.checked_char)
+        };
+
+        at(password.min_number) != at(password.max_number)
+    }
+}
+
+/// Policies checked by `Day2::report`, both puzzle policies are registered by default.
+pub struct Policies {
+    policies: Vec<Box<dyn PasswordPolicy>>,
+}
 
-        (self.min_number..=self.max_number).contains(&counter)
+impl Default for Policies {
+    fn default() -> Self {
+        let mut policies = Policies::empty();
+        policies.register(CountInRange);
+        policies.register(ExactlyOnePosition);
+        policies
     }
 }
 
+impl Policies {
+    pub fn empty() -> Self {
+        Policies { policies: Vec::new() }
+    }
+
+    pub fn register(&mut self, policy: impl PasswordPolicy + 'static) -> &mut Self {
+        self.policies.push(Box::new(policy));
+        self
+    }
+
+    pub fn report(&self, passwords: &[Password]) -> Vec<PolicyReport> {
+        self.policies
+            .iter()
+            .map(|policy| {
+                let failing_lines: Vec<usize> = passwords
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, password)| !policy.is_valid(password))
+                    .map(|(index, _)| index + 1)
+                    .collect();
+
+                PolicyReport {
+                    policy: policy.name().to_owned(),
+                    valid: passwords.len() - failing_lines.len(),
+                    failing_lines,
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PolicyReport {
+    pub policy:        String,
+    pub valid:         usize,
+    pub failing_lines: Vec<usize>,
+}
+
+/// `{:#}` lists the failing lines as well.
+impl fmt::Display for PolicyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} valid", self.policy, self.valid)?;
+
+        if f.alternate() && !self.failing_lines.is_empty() {
+            let lines: Vec<String> =
+                self.failing_lines.iter().map(|line| line.to_string()).collect();
+            write!(f, ", failing lines {}", lines.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+
 impl FromStr for Password {
     type Err = PasswordError;
 
@@ -54,16 +230,23 @@ impl FromStr for Password {
         let re: &Lazy<Regex> =
             regex!(r"(?P<min>\d+)-(?P<max>\d+) (?P<checked_char>\w{1}): (?P<passwd>\w+)");
         let caps = re.captures(s);
+        let number = |text: &str| {
+            text.parse::<u16>()
+                .map_err(|_| PasswordError::InvalidNumber(s.to_owned()))
+        };
 
         if let Some(r) = caps {
             Ok(Password {
-                min_number:   (&r["min"]).parse::<u16>().unwrap(),
-                max_number:   (&r["max"]).parse::<u16>().unwrap(),
-                checked_char: (&r["checked_char"]).chars().next().unwrap(),
+                min_number:   number(&r["min"])?,
+                max_number:   number(&r["max"])?,
+                checked_char: r["checked_char"]
+                    .chars()
+                    .next()
+                    .ok_or_else(|| PasswordError::CaptureFailed(s.to_owned()))?,
                 passwd:       (&r["passwd"]).to_string(),
             })
         } else {
-            Err(PasswordError::CaptureFailed)
+            Err(PasswordError::CaptureFailed(s.to_owned()))
         }
     }
 }
@@ -71,15 +254,16 @@ impl FromStr for Password {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::{self, InputSource};
     use test_case::test_case;
 
     #[test_case("1-3 a: abcde" => Ok(Password{min_number: 1, max_number: 3, checked_char: 'a', passwd: "abcde".to_string()}); "valid webiste 1")]
     #[test_case("1-3 b: cdefg" => Ok(Password{min_number: 1, max_number: 3, checked_char: 'b', passwd: "cdefg".to_string()}); "valid website 2")]
     #[test_case("2-9 c: ccccccccc" => Ok(Password{min_number: 2, max_number: 9, checked_char: 'c', passwd: "ccccccccc".to_string()}); "valid website 3")]
-    #[test_case("1-c a: abcde" => Err(PasswordError::CaptureFailed); "invalid letter as max_number")]
-    #[test_case("c-3 a: abcde" => Err(PasswordError::CaptureFailed); "invalid letter as min_number")]
+    #[test_case("1-c a: abcde" => Err(PasswordError::CaptureFailed("1-c a: abcde".to_owned())); "invalid letter as max_number")]
+    #[test_case("c-3 a: abcde" => Err(PasswordError::CaptureFailed("c-3 a: abcde".to_owned())); "invalid letter as min_number")]
     #[test_case("1-3 1: abcde" => Ok(Password{min_number: 1, max_number: 3, checked_char: '1', passwd: "abcde".to_string()}); "valid num as checked char")]
-    #[test_case("1-3 a: " => Err(PasswordError::CaptureFailed); "invalid lack of password")]
+    #[test_case("1-3 a: " => Err(PasswordError::CaptureFailed("1-3 a: ".to_owned())); "invalid lack of password")]
     fn test_from_str(s: &str) -> Result<Password, PasswordError> {
         s.parse::<Password>()
     }
@@ -88,10 +272,89 @@ mod tests {
     #[test_case("1-3 b: cdefg" =>  Ok(false)  ; "invalid 1-3 b: cdefg")]
     #[test_case("2-9 c: ccccccccc" =>  Ok(true)  ; "valid 2-9 c: ccccccccc")]
     #[test_case("3-1 a: abcde" => Ok(false) ; "invalid 3-1 a: abcde")]
-    #[test_case("1-c a: abcde" => Err(PasswordError::CaptureFailed); "invalid letter as max_number")]
-    #[test_case("c-3 a: abcde" => Err(PasswordError::CaptureFailed); "invalid letter as min_number")]
+    #[test_case("1-c a: abcde" => Err(PasswordError::CaptureFailed("1-c a: abcde".to_owned())); "invalid letter as max_number")]
+    #[test_case("c-3 a: abcde" => Err(PasswordError::CaptureFailed("c-3 a: abcde".to_owned())); "invalid letter as min_number")]
     fn test_is_valid(s: &str) -> Result<bool, PasswordError> {
         let p1 = s.parse::<Password>()?;
         Ok(p1.is_valid())
     }
+
+    #[test_case("1-3 a: abcde" => true; "first position")]
+    #[test_case("1-3 b: cdefg" => false; "no position")]
+    #[test_case("2-9 c: ccccccccc" => false; "both positions")]
+    #[test_case("3-9 e: abcde" => false; "position out of password")]
+    #[test_case("5-9 e: abcde" => true; "second position past the end")]
+    fn ex2_exactly_one_position(s: &str) -> bool {
+        ExactlyOnePosition.is_valid(&s.parse::<Password>().unwrap())
+    }
+
+    #[test]
+    fn ex2_solution_part_two() {
+        let input = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc";
+        assert_eq!(Day2::solution_part_two(input).unwrap(), 1);
+    }
+
+    struct ShorterThan(usize);
+
+    impl PasswordPolicy for ShorterThan {
+        fn name(&self) -> &str {
+            "shorter than"
+        }
+
+        fn is_valid(&self, password: &Password) -> bool {
+            password.passwd.len() < self.0
+        }
+    }
+
+    #[test]
+    fn ex2_report() {
+        let mut policies = Policies::default();
+        policies.register(ShorterThan(6));
+
+        let reports =
+            Day2::report("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc", &policies).unwrap();
+        let lines: Vec<String> = reports.iter().map(|report| format!("{:#}", report)).collect();
+
+        assert_eq!(
+            lines,
+            [
+                "count in range: 2 valid, failing lines 2",
+                "exactly one position: 1 valid, failing lines 2, 3",
+                "shorter than: 2 valid, failing lines 3",
+            ]
+        );
+        assert_eq!(reports[0].to_string(), "count in range: 2 valid");
+    }
+
+    #[test]
+    fn test_solution_counts_valid_passwords() {
+        assert_eq!(Day2::solve_default_file().unwrap(), 2)
+    }
+
+    #[test]
+    fn ex2_solution_reports_line() {
+        let err = Day2::solution("1-3 a: abcde\n1-99999 a: abcde").unwrap_err();
+
+        assert_eq!(
+            err.downcast::<PasswordError>().unwrap(),
+            PasswordError::InLine {
+                line:   2,
+                source: Box::new(PasswordError::InvalidNumber("1-99999 a: abcde".to_owned())),
+            }
+        );
+    }
+
+    #[test]
+    fn ex2_invalid_file_reports_line() {
+        let source = InputSource::from("data_files/ex2_invalid.txt");
+        let err = input::solve::<Day2>(&source).unwrap_err();
+
+        assert_eq!(
+            err.downcast::<PasswordError>().unwrap(),
+            PasswordError::InLine {
+                line:   4,
+                source: Box::new(PasswordError::CaptureFailed("1-c a: abcde".to_owned())),
+            }
+        );
+    }
 }
@@ -5,7 +5,9 @@ use fnv::FnvHashMap as HashMap;
 use itertools::{Itertools, Permutations};
 use std::fmt;
 use std::str::FromStr;
-use text_io::scan;
+use thiserror::Error;
+
+use super::diagnostic::{Diagnose, Locate};
 
 pub struct Day20;
 
@@ -19,17 +21,148 @@ impl DaySolver for Day20 {
         DayInfo::with_day_and_file_and_variant("day_20", "data_files/ex20.txt", "base");
 
     fn solution(_s: &str) -> anyhow::Result<<Self>::Output> {
-        let grid = _s.parse::<Grid>()?;
+        let grid = _s.parse::<Grid>().diagnose(_s)?;
 
         let mut pn = PossibleNeighborhoods::new();
         pn.fill(&grid.domains);
 
-        let res = grid.solve(&pn).unwrap();
+        let res = grid.solve(&pn).ok_or(Ex20Error::NoArrangement)?;
 
         Ok(res)
     }
 }
 
+pub const TILE_SIDE: usize = 10;
+const TILES_NUMBER: usize = 9;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex20Error {
+    #[error("invalid tile header `{0}`")]
+    InvalidHeader(String),
+
+    #[error("expected 10 rows of pixels, found {0}")]
+    WrongTileHeight(usize),
+
+    #[error("expected 10 pixels in row `{0}`")]
+    WrongRowLength(String),
+
+    #[error("invalid pixel in row `{0}`")]
+    InvalidPixel(String),
+
+    #[error("expected 9 tiles, found {0}")]
+    WrongTilesNumber(usize),
+
+    #[error("tiles can not be arranged into an image")]
+    NoArrangement,
+
+    #[error("line {line}: {source}")]
+    InLine {
+        line:   usize,
+        source: Box<Ex20Error>,
+    },
+}
+
+impl Locate for Ex20Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex20Error::InLine { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex20Error::InLine { source, .. } => source.fragment(),
+            Ex20Error::InvalidHeader(text) => Some(text.to_owned()),
+            Ex20Error::InvalidPixel(row) => row
+                .chars()
+                .find(|c| *c != '#' && *c != '.')
+                .map(String::from),
+            _ => None,
+        }
+    }
+}
+
+impl Ex20Error {
+    fn in_line(self, line: usize) -> Self {
+        match self {
+            Ex20Error::InLine { line: inner, source } => Ex20Error::InLine {
+                line: line + inner - 1,
+                source,
+            },
+            _ => Ex20Error::InLine {
+                line,
+                source: Box::new(self),
+            },
+        }
+    }
+}
+
+fn scan_tile_id(s: &str) -> Result<u128, text_io::Error> {
+    use text_io::try_scan;
+
+    let id: u128;
+    try_scan!(s.bytes() => "Tile {}:", id);
+
+    Ok(id)
+}
+
+fn check_row(row: &str) -> Result<(), Ex20Error> {
+    if row.len() != TILE_SIDE {
+        return Err(Ex20Error::WrongRowLength(row.to_owned()));
+    }
+
+    if !row.bytes().all(|pixel| pixel == b'#' || pixel == b'.') {
+        return Err(Ex20Error::InvalidPixel(row.to_owned()));
+    }
+
+    Ok(())
+}
+
+/// Validates one tile block and returns its id, line numbers are relative to the block.
+pub fn check_tile(s: &str) -> Result<u128, Ex20Error> {
+    let mut lines = s.lines().map(|line| line.trim());
+
+    let header = lines.next().unwrap_or_default();
+    let id = scan_tile_id(header)
+        .map_err(|_| Ex20Error::InvalidHeader(header.to_owned()).in_line(1))?;
+
+    let mut height = 0;
+    for (row_num, row) in lines.enumerate() {
+        check_row(row).map_err(|err| err.in_line(row_num + 2))?;
+        height += 1;
+    }
+
+    if height != TILE_SIDE {
+        return Err(Ex20Error::WrongTileHeight(height));
+    }
+
+    Ok(id)
+}
+
+/// Parses blank line separated tiles, errors carry line numbers of the whole input.
+pub fn parse_tiles<T>(s: &str) -> Result<Vec<T>, Ex20Error>
+where
+    T: FromStr<Err = Ex20Error>,
+{
+    let mut first_line = 1;
+    let mut tiles = Vec::new();
+
+    for block in s.split("\n\n") {
+        if !block.trim().is_empty() {
+            tiles.push(block.parse::<T>().map_err(|err| err.in_line(first_line))?);
+        }
+
+        first_line += block.lines().count() + 1;
+    }
+
+    if tiles.len() != TILES_NUMBER {
+        return Err(Ex20Error::WrongTilesNumber(tiles.len()));
+    }
+
+    Ok(tiles)
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum TileStage {
     Base,
@@ -71,13 +204,10 @@ struct Grid {
 }
 
 impl FromStr for Grid {
-    type Err = anyhow::Error;
+    type Err = Ex20Error;
 
     fn from_str(_s: &str) -> Result<Self, Self::Err> {
-        let tiles: Vec<Tile> = _s
-            .split("\n\n")
-            .map(|tile| tile.parse::<Tile>().unwrap())
-            .collect();
+        let tiles: Vec<Tile> = parse_tiles(_s)?;
 
         let domains = tiles.iter().map(|tile| tile.get_permutations()).collect();
 
@@ -271,7 +401,7 @@ impl fmt::Display for Tile {
 }
 
 impl FromStr for Tile {
-    type Err = anyhow::Error;
+    type Err = Ex20Error;
 
     /*
      -> 0001111110
@@ -291,11 +421,13 @@ impl FromStr for Tile {
     */
 
     fn from_str(_s: &str) -> Result<Self, Self::Err> {
+        let id = check_tile(_s)?;
+
         let res = _s.lines().map(|line| line.trim()).enumerate().fold(
             Tile::new(),
             |mut tile, (line_number, line)| match line_number {
                 0 => {
-                    scan!(line.bytes() => "Tile {}:", tile.id);
+                    tile.id = id;
                     tile
                 },
                 1 => {
@@ -330,19 +462,10 @@ impl FromStr for Tile {
 }
 
 pub fn calculate_borders_change(line: &str, line_number: usize) -> (u16, u16) {
-    let mut letters = line.chars();
-    let (first, last) = (letters.next().unwrap(), letters.last().unwrap());
+    let bit = 1 << (line_number - 1);
 
-    let mut left = 0;
-    let mut right = 0;
-
-    if first == '#' {
-        left += 2_u16.pow((line_number - 1).try_into().unwrap());
-    }
-
-    if last == '#' {
-        right += 2_u16.pow((line_number - 1).try_into().unwrap());
-    }
+    let left = if line.starts_with('#') { bit } else { 0 };
+    let right = if line.ends_with('#') { bit } else { 0 };
 
     (left, right)
 }
@@ -517,6 +640,27 @@ mod tests {
         assert_eq!(input.parse::<Tile>().unwrap(), res);
     }
 
+    #[test_case("Tle 2311:\n..##.#..#." => Ex20Error::InLine{line: 1, source: Box::new(Ex20Error::InvalidHeader("Tle 2311:".to_owned()))}; "header")]
+    #[test_case("Tile 2311:\n..##.#..#" => Ex20Error::InLine{line: 2, source: Box::new(Ex20Error::WrongRowLength("..##.#..#".to_owned()))}; "short row")]
+    #[test_case("Tile 2311:\n..##.#..x." => Ex20Error::InLine{line: 2, source: Box::new(Ex20Error::InvalidPixel("..##.#..x.".to_owned()))}; "pixel")]
+    #[test_case("Tile 2311:\n..##.#..#." => Ex20Error::WrongTileHeight(1); "height")]
+    fn ex20_tile_from_str_error(s: &str) -> Ex20Error {
+        s.parse::<Tile>().unwrap_err()
+    }
+
+    #[test]
+    fn ex20_parse_tiles_error_line() {
+        let input = std::fs::read_to_string("data_files/ex20.txt").unwrap();
+        let input = input.replacen("..##.#..#.", "..##.#..x.", 1);
+        let line = input.lines().position(|line| line == "..##.#..x.").unwrap() + 1;
+
+        let err = parse_tiles::<Tile>(&input).unwrap_err();
+        assert!(matches!(err, Ex20Error::InLine { line: found, .. } if found == line));
+
+        let err = parse_tiles::<Tile>("").unwrap_err();
+        assert_eq!(err, Ex20Error::WrongTilesNumber(0));
+    }
+
     #[test]
     fn ex20_tile_rotate() {
         let mut base = Tile {
@@ -1,17 +1,16 @@
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 
-use anyhow::anyhow;
 use either::Either;
 use fnv::FnvHashMap as HashMap;
 use std::fmt;
 use std::fmt::Debug;
 use std::str::FromStr;
-use text_io::scan;
 
+use super::diagnostic::Diagnose;
 use super::ex20::{
-    calculate_borders_change, get_next_rotation, get_next_stage, number_from_line, roatate_binary,
-    RotationState, TileStage,
+    calculate_borders_change, check_tile, get_next_rotation, get_next_stage, number_from_line,
+    parse_tiles, roatate_binary, Ex20Error, RotationState, TileStage,
 };
 
 pub struct Day20b;
@@ -20,13 +19,10 @@ impl DaySolver for Day20b {
     type Output = u128;
 
     const INFO: DayInfo =
-        DayInfo::with_day_and_file_and_variant("day_20b", "data_files/ex20.txt", "faster");
+        DayInfo::with_day_and_file_and_variant("day_20", "data_files/ex20.txt", "faster");
 
     fn solution(_s: &str) -> anyhow::Result<<Self>::Output> {
-        let tiles: Vec<Tile> = _s
-            .split("\n\n")
-            .map(|tile| tile.parse::<Tile>().unwrap())
-            .collect();
+        let tiles: Vec<Tile> = parse_tiles(_s).diagnose(_s)?;
 
         let domain_generator = DomainGenerator::from_tiles(&tiles);
         let grid = Grid::new();
@@ -37,7 +33,7 @@ impl DaySolver for Day20b {
                 Ok(res[8].id * res[7].id * res[6].id * res[5].id)
             },
 
-            Either::Right(_) => Err(anyhow!("Unable to solve")),
+            Either::Right(_) => Err(Ex20Error::NoArrangement.into()),
         }
     }
 }
@@ -58,14 +54,16 @@ impl fmt::Display for Tile {
 }
 
 impl FromStr for Tile {
-    type Err = anyhow::Error;
+    type Err = Ex20Error;
 
     fn from_str(_s: &str) -> Result<Self, Self::Err> {
+        let id = check_tile(_s)?;
+
         let res = _s.lines().map(|line| line.trim()).enumerate().fold(
             Tile::new(),
             |mut tile, (line_number, line)| match line_number {
                 0 => {
-                    scan!(line.bytes() => "Tile {}:", tile.id);
+                    tile.id = id;
                     tile
                 },
                 _ => {
@@ -232,7 +230,7 @@ impl DomainGenerator {
 
         for (tile, tile_neighbours) in connections.into_iter() {
             match tile_neighbours.len() {
-                4 => { // tiles with only 2 neighbors can be everywhere
+                4.. => { // tiles with 4 or more neighbors can be everywhere
                     for domain in domains.iter_mut().take(9) {
                         domain.push(tile);
                     }
@@ -248,9 +246,7 @@ impl DomainGenerator {
                     domains[6].push(tile);
                     domains[8].push(tile);
                 },
-                _ => {
-                    unreachable!();
-                },
+                _ => {}, // tiles with less than 2 neighbors can't be placed anywhere
             }
         }
 
@@ -424,7 +420,9 @@ where
     let mut solutions = vec![];
 
     while let Some(options) = stack.last_mut() {
-        let choice = options.pop().unwrap();
+        let Some(choice) = options.pop() else {
+            break; // no choices at all for the first tile
+        };
         here.choose(choice);
         bt_choices.push(choice);
         if here.is_goal() {
@@ -3,6 +3,9 @@ use aoc_utils::DaySolver;
 use fnv::FnvHashMap as HashMap;
 use fnv::FnvHashSet as HashSet;
 use std::str::FromStr;
+use thiserror::Error;
+
+use super::diagnostic::{Diagnose, Locate};
 
 pub struct Day21;
 
@@ -17,7 +20,7 @@ impl DaySolver for Day21 {
         DayInfo::with_day_and_file_and_variant("day_21", "data_files/ex21.txt", "base");
 
     fn solution(_s: &str) -> anyhow::Result<<Self>::Output> {
-        let foods = _s.parse::<Foods>().unwrap();
+        let foods = _s.parse::<Foods>().diagnose(_s)?;
 
         let allergens_sources = foods.get_possible_allergens_sources();
 
@@ -27,6 +30,37 @@ impl DaySolver for Day21 {
     }
 }
 
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex21Error {
+    #[error("missing allergens list in `{0}`")]
+    MissingAllergens(String),
+
+    #[error("unclosed allergens list in `{0}`")]
+    UnclosedAllergens(String),
+
+    #[error("line {line}: {source}")]
+    InLine {
+        line:   usize,
+        source: Box<Ex21Error>,
+    },
+}
+
+impl Locate for Ex21Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex21Error::InLine { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex21Error::InLine { source, .. } => source.fragment(),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 struct Food {
     allergens: Allergens,
@@ -34,10 +68,17 @@ struct Food {
 }
 
 impl FromStr for Food {
-    type Err = anyhow::Error;
+    type Err = Ex21Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (ingridients_unextracted, allergens_unextracted) = s.split_once(" (contains ").unwrap();
+        let (ingridients_unextracted, allergens_unextracted) = s
+            .split_once(" (contains ")
+            .ok_or_else(|| Ex21Error::MissingAllergens(s.to_owned()))?;
+
+        if !allergens_unextracted.ends_with(')') {
+            return Err(Ex21Error::UnclosedAllergens(s.to_owned()));
+        }
+
         let ingridients: Ingridients = ingridients_unextracted
             .split(' ')
             .map(|s| s.to_owned())
@@ -60,13 +101,19 @@ struct Foods {
 }
 
 impl FromStr for Foods {
-    type Err = anyhow::Error;
+    type Err = Ex21Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let foods: Vec<Food> = s
             .lines()
-            .map(|line| line.parse::<Food>().unwrap())
-            .collect();
+            .enumerate()
+            .map(|(line_num, line)| {
+                line.parse::<Food>().map_err(|err| Ex21Error::InLine {
+                    line:   line_num + 1,
+                    source: Box::new(err),
+                })
+            })
+            .collect::<Result<_, _>>()?;
 
         Ok(Foods { foods })
     }
@@ -131,6 +178,18 @@ mod tests {
         s.parse::<Food>().unwrap()
     }
 
+    #[test_case("sqjhc fvjkl" => Ex21Error::MissingAllergens("sqjhc fvjkl".to_owned()))]
+    #[test_case("sqjhc fvjkl (contains soy" => Ex21Error::UnclosedAllergens("sqjhc fvjkl (contains soy".to_owned()))]
+    fn ex21_food_from_str_error(s: &str) -> Ex21Error {
+        s.parse::<Food>().unwrap_err()
+    }
+
+    #[test]
+    fn ex21_foods_error_line() {
+        let err = "sqjhc fvjkl (contains soy)\nsqjhc".parse::<Foods>().err().unwrap();
+        assert!(matches!(err, Ex21Error::InLine { line: 2, .. }));
+    }
+
     #[test]
     fn ex21_get_possible_allergens_sources() {
         let t = r#"mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
@@ -2,6 +2,9 @@ use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 use std::collections::VecDeque;
 use std::str::FromStr;
+use thiserror::Error;
+
+use super::diagnostic::{Diagnose, Locate};
 
 pub struct Day22;
 
@@ -10,24 +13,27 @@ const INPUT_SECTION_DELIMETER: &str = "\n\n";
 type Card = u8;
 
 impl DaySolver for Day22 {
-    type Output = u16;
+    type Output = u32;
 
     const INFO: DayInfo =
         DayInfo::with_day_and_file_and_variant("day_22", "data_files/ex22.txt", "base");
 
     fn solution(_s: &str) -> anyhow::Result<<Self>::Output> {
-        let (p1_input, p2_input) = _s.split_once(INPUT_SECTION_DELIMETER).unwrap();
+        let (p1_input, p2_input) = _s
+            .split_once(INPUT_SECTION_DELIMETER)
+            .ok_or(Ex22Error::MissingSecondPlayer)?;
 
-        let mut player1 = p1_input.parse::<Player>().unwrap();
-        let mut player2 = p2_input.parse::<Player>().unwrap();
+        let mut player1 = parse_player(p1_input, 1).diagnose(_s)?;
+        let mut player2 =
+            parse_player(p2_input, p1_input.lines().count() + 2).diagnose(_s)?;
 
         let mut p1_card: Card;
         let mut p2_card: Card;
 
-        let res: u16 = loop {
+        let res: u32 = loop {
             // get value of first elements
-            p1_card = player1.get_card().unwrap();
-            p2_card = player2.get_card().unwrap();
+            p1_card = player1.get_card().ok_or(Ex22Error::EmptyDeck)?;
+            p2_card = player2.get_card().ok_or(Ex22Error::EmptyDeck)?;
 
             // game logic
             if p1_card > p2_card {
@@ -50,6 +56,41 @@ impl DaySolver for Day22 {
     }
 }
 
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex22Error {
+    #[error("missing deck of the second player")]
+    MissingSecondPlayer,
+
+    #[error("line {line}: invalid player header `{text}`")]
+    InvalidHeader { line: usize, text: String },
+
+    #[error("line {line}: invalid card `{text}`")]
+    InvalidCard { line: usize, text: String },
+
+    #[error("player starts with an empty deck")]
+    EmptyDeck,
+}
+
+impl Locate for Ex22Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex22Error::InvalidHeader { line, .. } | Ex22Error::InvalidCard { line, .. } => {
+                Some(*line)
+            },
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex22Error::InvalidHeader { text, .. } | Ex22Error::InvalidCard { text, .. } => {
+                Some(text.to_owned())
+            },
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug)]
 struct Player {
     cards: VecDeque<Card>,
@@ -68,33 +109,60 @@ impl Player {
         !self.cards.is_empty()
     }
 
-    fn count_result(&self) -> u16 {
+    fn count_result(&self) -> u32 {
         let deck_size = self.cards.len();
         self.cards
             .iter()
             .enumerate()
-            .map(|(index, value)| (((deck_size - index) as u8) * (*value)) as u16)
+            .map(|(index, value)| (deck_size - index) as u32 * u32::from(*value))
             .sum()
     }
 }
 
+/// `first_line` is the number of the header line in the whole input.
+fn parse_player(s: &str, first_line: usize) -> Result<Player, Ex22Error> {
+    let header = s.lines().next().unwrap_or_default();
+
+    if !(header.starts_with("Player ") && header.ends_with(':')) {
+        return Err(Ex22Error::InvalidHeader {
+            line: first_line,
+            text: header.to_owned(),
+        });
+    }
+
+    let cards: VecDeque<Card> = s
+        .lines()
+        .enumerate()
+        .skip(1)
+        .map(|(line_num, card)| {
+            card.trim()
+                .parse::<Card>()
+                .map_err(|_| Ex22Error::InvalidCard {
+                    line: first_line + line_num,
+                    text: card.to_owned(),
+                })
+        })
+        .collect::<Result<_, _>>()?;
+
+    if cards.is_empty() {
+        return Err(Ex22Error::EmptyDeck);
+    }
+
+    Ok(Player { cards })
+}
+
 impl FromStr for Player {
-    type Err = anyhow::Error;
+    type Err = Ex22Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let cards: VecDeque<Card> = s
-            .lines()
-            .skip(1)
-            .map(|card| card.parse::<u8>().unwrap())
-            .collect();
-
-        Ok(Player { cards })
+        parse_player(s, 1)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use test_case::test_case;
 
     #[test]
     fn ex22_count_result() {
@@ -115,6 +183,26 @@ mod tests {
         assert_eq!(input.parse::<Player>().unwrap().cards, result)
     }
 
+    #[test_case("Player 1:\n9\n2" => Ok(()); "valid")]
+    #[test_case("Player 1:\n9\nx" => Err(Ex22Error::InvalidCard{line: 3, text: "x".to_owned()}); "card")]
+    #[test_case("Gracz 1:\n9" => Err(Ex22Error::InvalidHeader{line: 1, text: "Gracz 1:".to_owned()}); "header")]
+    #[test_case("Player 1:" => Err(Ex22Error::EmptyDeck); "empty deck")]
+    fn ex22_deck_from_str_error(s: &str) -> Result<(), Ex22Error> {
+        s.parse::<Player>().map(|_| ())
+    }
+
+    #[test]
+    fn ex22_second_player_error_line() {
+        let err = Day22::solution("Player 1:\n9\n2\n\nPlayer 2:\n5\n-8").unwrap_err();
+        assert_eq!(
+            err.downcast::<Ex22Error>().unwrap(),
+            Ex22Error::InvalidCard {
+                line: 7,
+                text: "-8".to_owned()
+            }
+        );
+    }
+
     #[test]
     fn data_from_default_file() {
         assert_eq!(Day22::solve_default_file().unwrap(), 306)
@@ -3,6 +3,9 @@ use aoc_utils::DaySolver;
 use std::char::from_digit;
 use std::str;
 use std::str::FromStr;
+use thiserror::Error;
+
+use super::diagnostic::{Diagnose, Locate};
 
 pub struct Day23;
 
@@ -16,12 +19,71 @@ impl DaySolver for Day23 {
         DayInfo::with_day_and_file_and_variant("day_23", "data_files/ex23.txt", "base");
 
     fn solution(_s: &str) -> anyhow::Result<<Self>::Output> {
-        let mut game_engine = _s.parse::<GameEngine>().unwrap();
+        let mut game_engine = _s.parse::<GameEngine>().diagnose(_s)?;
 
         Ok(game_engine.run_game())
     }
 }
 
+const MIN_CUPS: usize = 5;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex23Error {
+    #[error("invalid cup label `{0}`")]
+    InvalidCup(char),
+
+    #[error("cup {0} appears more than once")]
+    DuplicatedCup(Cup),
+
+    #[error("expected at least 5 cups, found {0}")]
+    TooFewCups(usize),
+
+    #[error("missing cup {0}, labels have to be consecutive starting from 1")]
+    MissingCup(Cup),
+}
+
+/// Cups are given in a single line.
+impl Locate for Ex23Error {
+    fn line(&self) -> Option<usize> {
+        Some(1)
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex23Error::InvalidCup(label) => Some(label.to_string()),
+            Ex23Error::DuplicatedCup(cup) => Some(cup.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Cups have to be labeled with every digit from 1 to their number.
+pub fn parse_cups(s: &str) -> Result<Cups, Ex23Error> {
+    let mut cups: Cups = Vec::new();
+
+    for label in s.trim().chars() {
+        let cup = match label.to_digit(10) {
+            Some(cup) if cup > 0 => cup,
+            _ => return Err(Ex23Error::InvalidCup(label)),
+        };
+
+        if cups.contains(&cup) {
+            return Err(Ex23Error::DuplicatedCup(cup));
+        }
+
+        cups.push(cup);
+    }
+
+    if cups.len() < MIN_CUPS {
+        return Err(Ex23Error::TooFewCups(cups.len()));
+    }
+
+    match (1..=cups.len() as Cup).find(|cup| !cups.contains(cup)) {
+        Some(missing) => Err(Ex23Error::MissingCup(missing)),
+        None => Ok(cups),
+    }
+}
+
 #[derive(PartialEq, Debug)]
 struct GameEngine {
     cups:        Cups,
@@ -124,14 +186,11 @@ impl GameEngine {
 }
 
 impl FromStr for GameEngine {
-    type Err = anyhow::Error;
+    type Err = Ex23Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let cups: Cups = s
-            .chars()
-            .map(|cup| cup.to_digit(10).unwrap() as Cup)
-            .collect();
-        let min_cup: Cup = *cups.iter().min().unwrap();
+        let cups: Cups = parse_cups(s)?;
+        let min_cup: Cup = 1;
         let picked_cups: Cups = vec![0, 0, 0];
 
         Ok(GameEngine {
@@ -165,6 +224,16 @@ mod tests {
         assert_eq!("389125467".parse::<GameEngine>().unwrap(), res)
     }
 
+    #[test_case("389125467" => Ok(9); "valid")]
+    #[test_case("38912546a" => Err(Ex23Error::InvalidCup('a')); "letter")]
+    #[test_case("389125460" => Err(Ex23Error::InvalidCup('0')); "zero")]
+    #[test_case("389125463" => Err(Ex23Error::DuplicatedCup(3)); "duplicate")]
+    #[test_case("312" => Err(Ex23Error::TooFewCups(3)); "too few")]
+    #[test_case("389125" => Err(Ex23Error::MissingCup(4)); "gap")]
+    fn ex23_parse_cups(s: &str) -> Result<usize, Ex23Error> {
+        parse_cups(s).map(|cups| cups.len())
+    }
+
     #[test]
     fn ex23_get_result() {
         let mut game_engine = "389125467".parse::<GameEngine>().unwrap();
@@ -4,6 +4,9 @@ use std::char::from_digit;
 use std::str;
 use std::str::FromStr;
 
+use super::diagnostic::Diagnose;
+use super::ex23::{parse_cups, Ex23Error};
+
 /*
 We have:
       123456789|label
@@ -35,7 +38,7 @@ impl DaySolver for Day23b {
         DayInfo::with_day_and_file_and_variant("day_23", "data_files/ex23.txt", "new data struct");
 
     fn solution(_s: &str) -> anyhow::Result<<Self>::Output> {
-        let mut game_engine = _s.parse::<GameEngine>()?;
+        let mut game_engine = _s.parse::<GameEngine>().diagnose(_s)?;
 
         Ok(game_engine.run_game())
     }
@@ -156,14 +159,15 @@ impl GameEngine {
 }
 
 impl FromStr for GameEngine {
-    type Err = anyhow::Error;
+    type Err = Ex23Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut cups: Cups = vec![0; 9];
 
-        let mut input = s.chars().map(|cup| cup.to_digit(10).unwrap() as Cup);
-        let mut previous = input.next().unwrap();
-        let first = previous;
+        let labels = parse_cups(s)?;
+        let first = labels[0];
+        let mut previous = first;
+        let input = labels.into_iter().skip(1);
 
         for cup in input {
             cups[(previous - 1) as usize] = cup;
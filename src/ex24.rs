@@ -1,8 +1,48 @@
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
+use thiserror::Error;
+
+use super::diagnostic::{Diagnose, Locate};
 
 pub struct Day24;
 
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex24Error {
+    #[error("invalid direction `{0}`")]
+    InvalidDirection(String),
+
+    #[error("line {line}: {source}")]
+    InLine {
+        line:   usize,
+        source: Box<Ex24Error>,
+    },
+}
+
+impl Locate for Ex24Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex24Error::InLine { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex24Error::InLine { source, .. } => source.fragment(),
+            Ex24Error::InvalidDirection(direction) => Some(direction.to_owned()),
+        }
+    }
+}
+
+impl Ex24Error {
+    pub fn in_line(self, line: usize) -> Self {
+        Ex24Error::InLine {
+            line,
+            source: Box::new(self),
+        }
+    }
+}
+
 #[derive(PartialEq, Debug)]
 enum TileSide {
     White,
@@ -39,7 +79,7 @@ impl Tile {
         }
     }
 
-    fn shift(&mut self, direction: &[char]) {
+    fn shift(&mut self, direction: &[char]) -> Result<(), Ex24Error> {
         // https://math.stackexchange.com/questions/2254655/hexagon-grid-coordinate-system
         // Axial Coordinates
         match direction {
@@ -63,8 +103,12 @@ impl Tile {
             ['n', 'w'] => {
                 self.row -= 1;
             },
-            _ => unreachable!(),
+            _ => {
+                return Err(Ex24Error::InvalidDirection(direction.iter().collect()));
+            },
         }
+
+        Ok(())
     }
 }
 
@@ -75,46 +119,59 @@ impl DaySolver for Day24 {
         DayInfo::with_day_and_file_and_variant("day_24", "data_files/ex24.txt", "base");
 
     fn solution(_s: &str) -> anyhow::Result<<Self>::Output> {
-        let (black_tiles_counter, _) = _s.lines().map(get_tile).fold(
-            (0, Vec::new()),
-            |(black_tile_counter, mut tiles): (u32, Vec<Tile>), mut tile| {
-                if let Some(index) = tiles.iter().position(|element| *element == tile) {
-                    tiles[index].rotate();
-
-                    if tiles[index].color == TileSide::White {
-                        return (black_tile_counter - 1, tiles);
+        let (black_tiles_counter, _) = _s
+            .lines()
+            .enumerate()
+            .map(|(line_num, line)| get_tile(line).map_err(|err| err.in_line(line_num + 1)))
+            .collect::<Result<Vec<_>, _>>()
+            .diagnose(_s)?
+            .into_iter()
+            .fold(
+                (0, Vec::new()),
+                |(black_tile_counter, mut tiles): (u32, Vec<Tile>), mut tile| {
+                    if let Some(index) = tiles.iter().position(|element| *element == tile) {
+                        tiles[index].rotate();
+
+                        if tiles[index].color == TileSide::White {
+                            return (black_tile_counter - 1, tiles);
+                        }
+                    } else {
+                        tile.rotate();
+                        tiles.push(tile);
                     }
-                } else {
-                    tile.rotate();
-                    tiles.push(tile);
-                }
 
-                (black_tile_counter + 1, tiles)
-            },
-        );
+                    (black_tile_counter + 1, tiles)
+                },
+            );
 
         Ok(black_tiles_counter)
     }
 }
 
-fn get_tile(line: &str) -> Tile {
-    let (_, tile) = line
-        .chars()
-        .fold((None, Tile::new()), |(acc_letter, mut tile), new_letter| {
+fn get_tile(line: &str) -> Result<Tile, Ex24Error> {
+    let (dangling, tile) = line.chars().try_fold(
+        (None, Tile::new()),
+        |(acc_letter, mut tile), new_letter| -> Result<_, Ex24Error> {
             if new_letter == 'e' || new_letter == 'w' {
                 if let Some(old_letter) = acc_letter {
-                    tile.shift(&[old_letter, new_letter]);
+                    tile.shift(&[old_letter, new_letter])?;
                 } else {
-                    tile.shift(&[new_letter]);
+                    tile.shift(&[new_letter])?;
                 }
 
-                (None, tile)
+                Ok((None, tile))
+            } else if let Some(old_letter) = acc_letter {
+                Err(Ex24Error::InvalidDirection([old_letter, new_letter].iter().collect()))
             } else {
-                (Some(new_letter), tile)
+                Ok((Some(new_letter), tile))
             }
-        });
+        },
+    )?;
 
-    tile
+    match dangling {
+        Some(letter) => Err(Ex24Error::InvalidDirection(letter.to_string())),
+        None => Ok(tile),
+    }
 }
 
 #[cfg(test)]
@@ -126,7 +183,23 @@ mod tests {
     #[test_case("neeenesenwnwwswnenewnwwsewnenwseswesw" => Tile{column: 1, row: -3, color: TileSide::White})]
     #[test_case("seswneswswsenwwnwse" => Tile{column: -3, row: 3, color: TileSide::White})]
     fn ex24_get_tile(line: &str) -> Tile {
-        get_tile(line)
+        get_tile(line).unwrap()
+    }
+
+    #[test_case("esenx" => Ex24Error::InvalidDirection("nx".to_owned()); "unknown letter")]
+    #[test_case("xe" => Ex24Error::InvalidDirection("xe".to_owned()); "unknown pair")]
+    #[test_case("esen" => Ex24Error::InvalidDirection("n".to_owned()); "dangling")]
+    fn ex24_get_tile_error(line: &str) -> Ex24Error {
+        get_tile(line).unwrap_err()
+    }
+
+    #[test]
+    fn ex24_solution_error_line() {
+        let err = Day24::solution("esew\nnwwswee\nses").unwrap_err();
+        assert_eq!(
+            err.downcast::<Ex24Error>().unwrap(),
+            Ex24Error::InvalidDirection("s".to_owned()).in_line(3)
+        );
     }
 
     #[test_case(&['s', 'e'] => Tile{column: 0, row: 1, color: TileSide::White})]
@@ -137,7 +210,7 @@ mod tests {
     #[test_case(&['w'] => Tile{column: -1, row: 0, color: TileSide::White})]
     fn ex24_tile_shift(direction: &[char]) -> Tile {
         let mut tile = Tile::new();
-        tile.shift(direction);
+        tile.shift(direction).unwrap();
         tile
     }
 
@@ -2,6 +2,9 @@ use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 use fnv::FnvHashSet;
 
+use super::diagnostic::Diagnose;
+use super::ex24::Ex24Error;
+
 pub struct Day24b;
 
 #[derive(Debug, Eq, PartialEq, Hash)]
@@ -15,7 +18,7 @@ impl TilePosition {
         Self { column: 0, row: 0 }
     }
 
-    fn shift(&mut self, direction: &[char]) {
+    fn shift(&mut self, direction: &[char]) -> Result<(), Ex24Error> {
         // https://math.stackexchange.com/questions/2254655/hexagon-grid-coordinate-system
         // Axial Coordinates
         match direction {
@@ -39,8 +42,12 @@ impl TilePosition {
             ['n', 'w'] => {
                 self.row -= 1;
             },
-            _ => unreachable!(),
+            _ => {
+                return Err(Ex24Error::InvalidDirection(direction.iter().collect()));
+            },
         }
+
+        Ok(())
     }
 }
 
@@ -48,43 +55,57 @@ impl DaySolver for Day24b {
     type Output = u32;
 
     const INFO: DayInfo =
-        DayInfo::with_day_and_file_and_variant("day_24", "data_files/ex24.txt", "base");
+        DayInfo::with_day_and_file_and_variant("day_24", "data_files/ex24.txt", "cleaned");
 
     fn solution(s: &str) -> anyhow::Result<<Self>::Output> {
-        let black_tiles = s.lines().map(get_tile_position).fold(
-            FnvHashSet::default(),
-            |mut black_tiles: FnvHashSet<TilePosition>, tile_position| {
-                if !black_tiles.remove(&tile_position) {
-                    black_tiles.insert(tile_position);
-                }
-
-                black_tiles
-            },
-        );
+        let black_tiles = s
+            .lines()
+            .enumerate()
+            .map(|(line_num, line)| {
+                get_tile_position(line).map_err(|err| err.in_line(line_num + 1))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .diagnose(s)?
+            .into_iter()
+            .fold(
+                FnvHashSet::default(),
+                |mut black_tiles: FnvHashSet<TilePosition>, tile_position| {
+                    if !black_tiles.remove(&tile_position) {
+                        black_tiles.insert(tile_position);
+                    }
+
+                    black_tiles
+                },
+            );
 
         Ok(black_tiles.len() as u32)
     }
 }
 
-fn get_tile_position(line: &str) -> TilePosition {
-    let (_, tile) = line.chars().fold(
+fn get_tile_position(line: &str) -> Result<TilePosition, Ex24Error> {
+    let (dangling, tile) = line.chars().try_fold(
         (None, TilePosition::new()),
-        |(acc_letter, mut tile), new_letter| {
+        |(acc_letter, mut tile), new_letter| -> Result<_, Ex24Error> {
             if new_letter == 'e' || new_letter == 'w' {
                 if let Some(old_letter) = acc_letter {
-                    tile.shift(&[old_letter, new_letter]);
+                    tile.shift(&[old_letter, new_letter])?;
                 } else {
-                    tile.shift(&[new_letter]);
+                    tile.shift(&[new_letter])?;
                 }
 
-                (None, tile)
+                Ok((None, tile))
+            } else if let Some(old_letter) = acc_letter {
+                Err(Ex24Error::InvalidDirection([old_letter, new_letter].iter().collect()))
             } else {
-                (Some(new_letter), tile)
+                Ok((Some(new_letter), tile))
             }
         },
-    );
+    )?;
 
-    tile
+    match dangling {
+        Some(letter) => Err(Ex24Error::InvalidDirection(letter.to_string())),
+        None => Ok(tile),
+    }
 }
 
 #[cfg(test)]
@@ -96,7 +117,7 @@ mod tests {
     #[test_case("neeenesenwnwwswnenewnwwsewnenwseswesw" => TilePosition{column: 1, row: -3})]
     #[test_case("seswneswswsenwwnwse" => TilePosition{column: -3, row: 3})]
     fn ex24_get_tile_position(line: &str) -> TilePosition {
-        get_tile_position(line)
+        get_tile_position(line).unwrap()
     }
 
     #[test_case(&['s', 'e'] => TilePosition{column: 0, row: 1})]
@@ -107,7 +128,7 @@ mod tests {
     #[test_case(&['w'] => TilePosition{column: -1, row: 0})]
     fn ex24_tile_shift(direction: &[char]) -> TilePosition {
         let mut tile = TilePosition::new();
-        tile.shift(direction);
+        tile.shift(direction).unwrap();
         tile
     }
 
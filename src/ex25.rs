@@ -1,7 +1,11 @@
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
+use thiserror::Error;
+
+use super::diagnostic::{Diagnose, Locate};
 
 const MODULO: u128 = 20201227;
+const SUBJECT_NUMBER: u128 = 7;
 
 pub struct Day25;
 
@@ -12,14 +16,11 @@ impl DaySolver for Day25 {
         DayInfo::with_day_and_file_and_variant("day_25", "data_files/ex25.txt", "base");
 
     fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
-        let mut iter = _s.lines().map(|linse| linse.parse::<u128>().unwrap());
-
-        let mut public_key1 = iter.next().unwrap();
-        let public_key2 = iter.next().unwrap();
+        let (public_key1, public_key2) = parse_public_keys(_s).diagnose(_s)?;
+        let mut public_key1 = u128::from(public_key1);
 
-        let mut loop_size_key2 = (0..u32::MAX)
-            .find(|loop_size| 7_u128.pow(*loop_size) % MODULO == public_key2)
-            .unwrap();
+        let mut loop_size_key2 =
+            loop_size(u128::from(public_key2)).ok_or(Ex25Error::NoLoopSize(public_key2))?;
 
         let mut result = 1;
 
@@ -36,9 +37,101 @@ impl DaySolver for Day25 {
     }
 }
 
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex25Error {
+    #[error("line {line}: invalid public key `{text}`")]
+    InvalidKey { line: usize, text: String },
+
+    #[error("line {line}: public key {key} is out of range 1..20201227")]
+    KeyOutOfRange { line: usize, key: u64 },
+
+    #[error("expected 2 public keys, found {0}")]
+    WrongKeysNumber(usize),
+
+    #[error("no loop size gives public key {0}")]
+    NoLoopSize(u64),
+}
+
+impl Locate for Ex25Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex25Error::InvalidKey { line, .. } | Ex25Error::KeyOutOfRange { line, .. } => {
+                Some(*line)
+            },
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex25Error::InvalidKey { text, .. } => Some(text.trim().to_owned()),
+            Ex25Error::KeyOutOfRange { key, .. } => Some(key.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Keys of the card and the door, both have to be smaller than the modulo.
+pub fn parse_public_keys(s: &str) -> Result<(u64, u64), Ex25Error> {
+    let keys = s
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(line_num, line)| {
+            let key = line.trim().parse::<u64>().map_err(|_| Ex25Error::InvalidKey {
+                line: line_num + 1,
+                text: line.to_owned(),
+            })?;
+
+            if key == 0 || u128::from(key) >= MODULO {
+                return Err(Ex25Error::KeyOutOfRange {
+                    line: line_num + 1,
+                    key,
+                });
+            }
+
+            Ok(key)
+        })
+        .collect::<Result<Vec<u64>, Ex25Error>>()?;
+
+    match keys[..] {
+        [card, door] => Ok((card, door)),
+        _ => Err(Ex25Error::WrongKeysNumber(keys.len())),
+    }
+}
+
+fn loop_size(public_key: u128) -> Option<u128> {
+    let mut value = 1;
+
+    for loop_size in 0..MODULO {
+        if value == public_key {
+            return Some(loop_size);
+        }
+
+        value = value * SUBJECT_NUMBER % MODULO;
+    }
+
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use test_case::test_case;
+
+    #[test_case("5764801\n17807724" => Ok((5764801, 17807724)); "valid")]
+    #[test_case("5764801\nkey" => Err(Ex25Error::InvalidKey{line: 2, text: "key".to_owned()}); "invalid")]
+    #[test_case("5764801\n20201227" => Err(Ex25Error::KeyOutOfRange{line: 2, key: 20201227}); "too large")]
+    #[test_case("5764801" => Err(Ex25Error::WrongKeysNumber(1)); "missing key")]
+    fn ex25_parse_public_keys(s: &str) -> Result<(u64, u64), Ex25Error> {
+        parse_public_keys(s)
+    }
+
+    #[test_case(5764801 => Some(8))]
+    #[test_case(17807724 => Some(11))]
+    fn ex25_loop_size(public_key: u128) -> Option<u128> {
+        loop_size(public_key)
+    }
 
     #[test]
     fn data_from_default_file() {
@@ -1,6 +1,9 @@
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 
+use super::diagnostic::Diagnose;
+use super::ex25::parse_public_keys;
+
 const SUBJECT_NUMBER: u64 = 7;
 
 pub struct Day25b;
@@ -12,10 +15,8 @@ impl DaySolver for Day25b {
         DayInfo::with_day_and_file_and_variant("day_25", "data_files/ex25.txt", "perf");
 
     fn solution(s: &str) -> anyhow::Result<Self::Output> {
-        let public_keys: Vec<u64> = s
-            .lines()
-            .filter_map(|line| line.parse::<u64>().ok())
-            .collect();
+        let (card_key, door_key) = parse_public_keys(s).diagnose(s)?;
+        let public_keys = [card_key, door_key];
 
         let mut pk_1 = 1_u64;
         let mut pk_2 = 1_u64;
@@ -1,9 +1,11 @@
-use anyhow::Context;
 use thiserror::Error;
 
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 
+use super::diagnostic::{Diagnose, Locate};
+use super::part::PartTwo;
+
 pub struct Day3;
 
 impl DaySolver for Day3 {
@@ -12,21 +14,69 @@ impl DaySolver for Day3 {
     const INFO: DayInfo = DayInfo::with_day_and_file("day_3", "data_files/ex3.txt");
 
     fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
-        let res = count_trees(11, 11, 3, _s)?;
-        Ok(res)
+        let (width, height) = board_size(_s);
+        let res = count_trees(width, height, &[(3, 1)], _s)?;
+        Ok(res[0])
     }
 }
 
+impl PartTwo for Day3 {
+    type OutputPartTwo = u64;
+
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+        let (width, height) = board_size(s);
+        let res = count_trees(width, height, &PART_TWO_SLOPES, s)?;
+        Ok(trees_product(&res))
+    }
+}
+
+/// Steps to the right and down made on every move.
+pub type Slope = (usize, usize);
+
+pub const PART_TWO_SLOPES: [Slope; 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum Ex3Error {
-    #[error("not allowed char occured")]
-    InvalidChar,
+    #[error("not allowed char occured in `{0}`")]
+    InvalidChar(String),
 
-    #[error("unacceptable line len")]
-    WrongLen,
+    #[error("unacceptable line len of `{0}`")]
+    WrongLen(String),
+
+    #[error("line {line}: {source}")]
+    InLine {
+        line:   usize,
+        source: Box<Ex3Error>,
+    },
 
     #[error("to low number of lines in given file (expected {expected:?}, found {found:?})")]
     NotEnaughtLines { expected: usize, found: usize },
+
+    #[error("slope ({0}, 0) never leaves the first row")]
+    NoDownStep(usize),
+
+    #[error("map has no columns")]
+    EmptyMap,
+}
+
+impl Locate for Ex3Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex3Error::InLine { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex3Error::InLine { source, .. } => source.fragment(),
+            Ex3Error::InvalidChar(line) => line
+                .chars()
+                .find(|c| *c != '.' && *c != '#')
+                .map(String::from),
+            _ => None,
+        }
+    }
 }
 
 fn process_line(line: &str, expexted_len: usize) -> Result<String, Ex3Error> {
@@ -34,62 +84,76 @@ fn process_line(line: &str, expexted_len: usize) -> Result<String, Ex3Error> {
 
     // check if line has proper len
     if cuted.len() != expexted_len {
-        return Err(Ex3Error::WrongLen);
+        return Err(Ex3Error::WrongLen(line.to_owned()));
     }
 
     // check if unexpected chars occured
     if cuted.chars().any(|c| c != '.' && c != '#') {
-        return Err(Ex3Error::InvalidChar);
+        return Err(Ex3Error::InvalidChar(line.to_owned()));
     }
 
     Ok(cuted.to_string())
 }
 
-fn count_trees(
+/// Width of the first row and number of rows of the map in `input`.
+pub fn board_size(input: &str) -> (usize, usize) {
+    let width = input.lines().next().map_or(0, |line| line.trim().len());
+    (width, input.lines().count())
+}
+
+/// Trees met on each of the `slopes`, only the first `board_height` lines make the map.
+pub fn count_trees(
     board_width: usize,
     board_height: usize,
-    step: usize,
+    slopes: &[Slope],
     input: &str
-) -> anyhow::Result<u32>
+) -> anyhow::Result<Vec<u32>>
 {
-    let mut index: usize = 0;
-    let mut skip_to_line: usize = 0;
-    let mut trees_num: u32 = 0;
-    let mut curr_line: usize = 0;
-
-    for (line_num, line) in input.lines().enumerate() {
-        let p = process_line(line, board_width)
-            .with_context(|| format!("line content: {} (line={})", line, line_num))?;
-
-        curr_line = line_num % board_height;
-
-        if line_num > skip_to_line || skip_to_line == 0 {
-            if p.chars().nth(index).unwrap() == '#' {
-                trees_num += 1;
-            }
-            index += step;
+    if let Some((right, _)) = slopes.iter().find(|(_, down)| *down == 0) {
+        return Err(Ex3Error::NoDownStep(*right).into());
+    }
 
-            if index >= board_width {
-                index %= board_width;
-                skip_to_line = line_num + board_height;
-            }
+    if board_width == 0 {
+        return Err(Ex3Error::EmptyMap.into());
+    }
 
-            if (line_num + 1) % board_height == 0 {
-                break;
-            }
+    let map = input
+        .lines()
+        .take(board_height)
+        .enumerate()
+        .map(|(line_num, line)| {
+            process_line(line, board_width).map_err(|err| Ex3Error::InLine {
+                line:   line_num + 1,
+                source: Box::new(err),
+            })
+        })
+        .collect::<Result<Vec<String>, Ex3Error>>()
+        .diagnose(input)?;
+
+    if map.len() < board_height {
+        return Err(Ex3Error::NotEnaughtLines {
+            expected: board_height,
+            found:    map.len(),
         }
+        .into());
     }
-    if curr_line + 1 < board_height {
-        return Err(anyhow::anyhow!(
-            "{}",
-            Ex3Error::NotEnaughtLines {
-                expected: board_height,
-                found:    curr_line + 1,
-            }
-        ));
-    }
 
-    Ok(trees_num)
+    let res = slopes
+        .iter()
+        .map(|&(right, down)| {
+            map.iter()
+                .step_by(down)
+                .enumerate()
+                .filter(|(step, row)| row.as_bytes()[(step * right) % board_width] == b'#')
+                .count() as u32
+        })
+        .collect();
+
+    Ok(res)
+}
+
+pub fn trees_product(trees: &[u32]) -> u64 {
+    trees.iter().map(|trees_num| u64::from(*trees_num)).product()
 }
 
 #[cfg(test)]
@@ -97,20 +161,39 @@ mod tests {
     use super::*;
     use test_case::test_case;
 
-    #[test_case("..#.##.....", 20 => Err(Ex3Error::WrongLen); "wrong len (to short))")]
-    #[test_case("..#.##.....", 2 => Err(Ex3Error::WrongLen); "wrong len (to long))")]
+    #[test_case("..#.##.....", 20 => Err(Ex3Error::WrongLen("..#.##.....".to_owned())); "wrong len (to short))")]
+    #[test_case("..#.##.....", 2 => Err(Ex3Error::WrongLen("..#.##.....".to_owned())); "wrong len (to long))")]
     #[test_case("..#.##.....", 11 => Ok("..#.##.....".to_string()); "ok len")]
     #[test_case("  ..#.##.....", 11 => Ok("..#.##.....".to_string()); "trim left")]
     #[test_case("..#.##.....   ", 11 => Ok("..#.##.....".to_string()); "trim right")]
     #[test_case("  ..#.##.....  ", 11 => Ok("..#.##.....".to_string()); "trim both")]
-    #[test_case(".*#.##.....", 11 => Err(Ex3Error::InvalidChar); "invalid char")]
+    #[test_case(".*#.##.....", 11 => Err(Ex3Error::InvalidChar(".*#.##.....".to_owned())); "invalid char")]
     fn test_is_valid(s: &str, expexted_len: usize) -> Result<String, Ex3Error> {
         process_line(s, expexted_len)
     }
 
     #[test]
     fn test_count_trees_no_file() {
-        assert!(count_trees(1, 1, 1, "aaa").is_err())
+        assert!(count_trees(1, 1, &[(1, 1)], "aaa").is_err())
+    }
+
+    #[test]
+    fn test_count_trees_no_down_step() {
+        let err = count_trees(2, 2, &[(1, 1), (3, 0)], "..\n..").unwrap_err();
+        assert_eq!(err.downcast::<Ex3Error>().unwrap(), Ex3Error::NoDownStep(3));
+    }
+
+    #[test]
+    fn test_count_trees_reports_line() {
+        let err = count_trees(2, 2, &[(1, 1)], "..\n.x").unwrap_err();
+
+        assert_eq!(
+            err.downcast::<Ex3Error>().unwrap(),
+            Ex3Error::InLine {
+                line:   2,
+                source: Box::new(Ex3Error::InvalidChar(".x".to_owned())),
+            }
+        );
     }
 
     #[test]
@@ -118,13 +201,47 @@ mod tests {
         use aoc_utils::read_to_string;
         let input = read_to_string("data_files/ex3.txt").unwrap();
         assert_eq!(
-            count_trees(11, 11, 3, &input).unwrap(),
-            7
+            count_trees(11, 11, &[(3, 1)], &input).unwrap(),
+            vec![7]
         );
         assert_eq!(
-            count_trees(11, 11, 0, &input).unwrap(),
-            3
+            count_trees(11, 11, &[(0, 1)], &input).unwrap(),
+            vec![3]
         );
-        assert!(count_trees(11, 110, 0, &input).is_err());
+        assert!(count_trees(11, 110, &[(0, 1)], &input).is_err());
+    }
+
+    #[test]
+    fn test_count_trees_slopes() {
+        use aoc_utils::read_to_string;
+        let input = read_to_string("data_files/ex3.txt").unwrap();
+        let trees = count_trees(11, 11, &PART_TWO_SLOPES, &input).unwrap();
+
+        assert_eq!(trees, vec![2, 7, 4, 4, 2]);
+        assert_eq!(trees_product(&trees), 448);
+        assert_eq!(count_trees(11, 11, &[(1, 4)], &input).unwrap(), vec![2]);
+    }
+
+    #[test_case("..#\n#..\n.#." => (3, 3); "square")]
+    #[test_case("  ..#.#  \n.#...\n#...." => (5, 3); "trimmed first row")]
+    #[test_case("" => (0, 0); "empty")]
+    fn test_board_size(s: &str) -> (usize, usize) {
+        board_size(s)
+    }
+
+    #[test_case("" ; "no lines")]
+    #[test_case("\n" ; "empty first line")]
+    #[test_case("\n..#" ; "empty first line before map")]
+    fn test_solution_empty_map(s: &str) {
+        let err = Day3::solution(s).unwrap_err();
+        assert_eq!(err.downcast::<Ex3Error>().unwrap(), Ex3Error::EmptyMap);
+    }
+
+    #[test]
+    fn test_solution_uses_whole_map() {
+        let input = aoc_utils::read_to_string("data_files/ex3.txt").unwrap();
+
+        assert_eq!(Day3::solution(&input).unwrap(), 28);
+        assert_eq!(Day3::solution_part_two(&input).unwrap(), 326144);
     }
 }
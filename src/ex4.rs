@@ -1,6 +1,15 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use thiserror::Error;
+
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 
+use super::diagnostic::{Diagnose, Locate};
+use super::part::PartTwo;
+
 pub struct Day4;
 
 impl DaySolver for Day4 {
@@ -9,50 +18,433 @@ impl DaySolver for Day4 {
     const INFO: DayInfo = DayInfo::with_day_and_file("day_4", "data_files/ex4.txt");
 
     fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
-        let mut mapper = 0;
-        let mut counter: usize = 0;
+        let passports = parse_passports(_s).diagnose(_s)?;
+
+        let res = passports
+            .iter()
+            .filter(|passport| passport.has_required_fields())
+            .count();
+
+        Ok(res)
+    }
+}
+
+impl PartTwo for Day4 {
+    type OutputPartTwo = usize;
+
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+        let passports = parse_passports(s).diagnose(s)?;
+
+        let res = passports
+            .iter()
+            .filter(|passport| passport.is_valid())
+            .count();
+
+        Ok(res)
+    }
+}
+
+impl Day4 {
+    /// Every passport failing the part two rules, with the first rule it broke.
+    pub fn report(s: &str) -> anyhow::Result<Vec<InvalidPassport>> {
+        let passports = parse_passports(s).diagnose(s)?;
+        Ok(report(&passports))
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex4Error {
+    #[error("field `{0}` is not in `key:value` form")]
+    MissingSeparator(String),
+
+    #[error("unknown field `{0}`")]
+    UnknownField(String),
+
+    #[error("field `{0}` given twice")]
+    DuplicatedField(String),
+
+    #[error("line {line}: {source}")]
+    InLine {
+        line:   usize,
+        source: Box<Ex4Error>,
+    },
+}
+
+impl Locate for Ex4Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex4Error::InLine { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex4Error::InLine { source, .. } => source.fragment(),
+            Ex4Error::MissingSeparator(field)
+            | Ex4Error::UnknownField(field)
+            | Ex4Error::DuplicatedField(field) => Some(field.to_owned()),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Field {
+    BirthYear,
+    IssueYear,
+    ExpirationYear,
+    Height,
+    HairColor,
+    EyeColor,
+    PassportId,
+    CountryId,
+}
 
-        for line in _s.lines() {
-            if line.is_empty() {
-                if mapper == 127 {
-                    counter += 1;
+impl Field {
+    pub fn key(self) -> &'static str {
+        match self {
+            Field::BirthYear => "byr",
+            Field::IssueYear => "iyr",
+            Field::ExpirationYear => "eyr",
+            Field::Height => "hgt",
+            Field::HairColor => "hcl",
+            Field::EyeColor => "ecl",
+            Field::PassportId => "pid",
+            Field::CountryId => "cid",
+        }
+    }
+}
+
+impl FromStr for Field {
+    type Err = Ex4Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "byr" => Ok(Field::BirthYear),
+            "iyr" => Ok(Field::IssueYear),
+            "eyr" => Ok(Field::ExpirationYear),
+            "hgt" => Ok(Field::Height),
+            "hcl" => Ok(Field::HairColor),
+            "ecl" => Ok(Field::EyeColor),
+            "pid" => Ok(Field::PassportId),
+            "cid" => Ok(Field::CountryId),
+            _ => Err(Ex4Error::UnknownField(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rule {
+    Year(RangeInclusive<u32>),
+    Height {
+        cm:   RangeInclusive<u32>,
+        inch: RangeInclusive<u32>,
+    },
+    HexColor,
+    OneOf(&'static [&'static str]),
+    Digits(usize),
+}
+
+/// Required fields and the part two rule of each of them, `cid` is optional.
+pub const RULES: [(Field, Rule); 7] = [
+    (Field::BirthYear, Rule::Year(1920..=2002)),
+    (Field::IssueYear, Rule::Year(2010..=2020)),
+    (Field::ExpirationYear, Rule::Year(2020..=2030)),
+    (Field::Height, Rule::Height { cm: 150..=193, inch: 59..=76 }),
+    (Field::HairColor, Rule::HexColor),
+    (Field::EyeColor, Rule::OneOf(&["amb", "blu", "brn", "gry", "grn", "hzl", "oth"])),
+    (Field::PassportId, Rule::Digits(9)),
+];
+
+impl Rule {
+    pub fn check(&self, value: &str) -> bool {
+        let number_in = |number: &str, range: &RangeInclusive<u32>| {
+            number.parse::<u32>().is_ok_and(|number| range.contains(&number))
+        };
+
+        match self {
+            Rule::Year(years) => value.len() == 4 && number_in(value, years),
+            Rule::Height { cm, inch } => {
+                if let Some(height) = value.strip_suffix("cm") {
+                    number_in(height, cm)
+                } else if let Some(height) = value.strip_suffix("in") {
+                    number_in(height, inch)
+                } else {
+                    false
                 }
+            },
+            Rule::HexColor => value.strip_prefix('#').is_some_and(|color| {
+                color.len() == 6 && color.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f'))
+            }),
+            Rule::OneOf(values) => values.contains(&value),
+            Rule::Digits(len) => value.len() == *len && value.chars().all(|c| c.is_ascii_digit()),
+        }
+    }
+}
 
-                mapper = 0;
-            } else {
-                let m: i16 = line
-                    .split(' ')
-                    .map(|e| e.split_once(':').unwrap().0)
-                    .map(|key| match key {
-                        "byr" => 1,
-                        "iyr" => 2,
-                        "eyr" => 4,
-                        "hgt" => 8,
-                        "hcl" => 16,
-                        "ecl" => 32,
-                        "pid" => 64,
-                        "cid" => 0,
-                        _ => unreachable!(),
-                    })
-                    .sum();
-                mapper += m;
-            }
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::Year(years) => write!(f, "year in {:?}", years),
+            Rule::Height { cm, inch } => write!(f, "height in {:?} cm or {:?} in", cm, inch),
+            Rule::HexColor => write!(f, "`#` followed by 6 of 0-9 or a-f"),
+            Rule::OneOf(values) => write!(f, "one of {}", values.join(", ")),
+            Rule::Digits(len) => write!(f, "{} digits", len),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Passport {
+    /// First line of the passport in the batch file, starting from 1.
+    pub line:   usize,
+    pub fields: BTreeMap<Field, String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Violation {
+    Missing(Field),
+    Broken {
+        field: Field,
+        value: String,
+        rule:  Rule,
+    },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Missing(field) => write!(f, "missing `{}`", field),
+            Violation::Broken { field, value, rule } => {
+                write!(f, "`{}:{}` is not {}", field, value, rule)
+            },
+        }
+    }
+}
+
+impl Passport {
+    pub fn has_required_fields(&self) -> bool {
+        RULES.iter().all(|(field, _)| self.fields.contains_key(field))
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.violation().is_none()
+    }
+
+    /// First broken rule, missing fields are reported before invalid values.
+    pub fn violation(&self) -> Option<Violation> {
+        if let Some((field, _)) = RULES.iter().find(|(field, _)| !self.fields.contains_key(field)) {
+            return Some(Violation::Missing(*field));
         }
 
-        if mapper == 127 {
-            counter += 1;
+        RULES.iter().find_map(|(field, rule)| {
+            let value = &self.fields[field];
+
+            (!rule.check(value)).then(|| Violation::Broken {
+                field: *field,
+                value: value.to_owned(),
+                rule:  rule.clone(),
+            })
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvalidPassport {
+    pub line:      usize,
+    pub violation: Violation,
+}
+
+impl fmt::Display for InvalidPassport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "passport at line {}: {}", self.line, self.violation)
+    }
+}
+
+pub fn report(passports: &[Passport]) -> Vec<InvalidPassport> {
+    passports
+        .iter()
+        .filter_map(|passport| {
+            Some(InvalidPassport {
+                line:      passport.line,
+                violation: passport.violation()?,
+            })
+        })
+        .collect()
+}
+
+/// Passports are separated by empty lines, their fields by spaces or new lines.
+pub fn parse_passports(s: &str) -> Result<Vec<Passport>, Ex4Error> {
+    let mut passports: Vec<Passport> = Vec::new();
+    let mut current: Option<Passport> = None;
+
+    for (line_num, line) in s.lines().enumerate() {
+        if line.trim().is_empty() {
+            passports.extend(current.take());
+            continue;
         }
 
-        Ok(counter)
+        let passport = current.get_or_insert_with(|| Passport {
+            line:   line_num + 1,
+            fields: BTreeMap::new(),
+        });
+
+        for field in line.split_whitespace() {
+            let (field, value) = parse_field(field).map_err(|err| Ex4Error::InLine {
+                line:   line_num + 1,
+                source: Box::new(err),
+            })?;
+
+            if passport.fields.insert(field, value.to_owned()).is_some() {
+                return Err(Ex4Error::InLine {
+                    line:   line_num + 1,
+                    source: Box::new(Ex4Error::DuplicatedField(field.key().to_owned())),
+                });
+            }
+        }
     }
+
+    passports.extend(current);
+
+    Ok(passports)
+}
+
+fn parse_field(field: &str) -> Result<(Field, &str), Ex4Error> {
+    let (key, value) = field
+        .split_once(':')
+        .ok_or_else(|| Ex4Error::MissingSeparator(field.to_owned()))?;
+
+    let key = key
+        .parse::<Field>()
+        .map_err(|_| Ex4Error::UnknownField(field.to_owned()))?;
+
+    Ok((key, value))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use test_case::test_case;
+
+    const INVALID_PASSPORTS: &str = "eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007";
+
+    const VALID_PASSPORTS: &str = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
 
     #[test]
     fn data_from_default_file() {
         assert_eq!(Day4::solve_default_file().unwrap(), 2)
     }
+
+    #[test_case(Field::BirthYear, "2002" => true)]
+    #[test_case(Field::BirthYear, "2003" => false)]
+    #[test_case(Field::BirthYear, "02002" => false)]
+    #[test_case(Field::Height, "60in" => true)]
+    #[test_case(Field::Height, "190cm" => true)]
+    #[test_case(Field::Height, "190in" => false)]
+    #[test_case(Field::Height, "190" => false)]
+    #[test_case(Field::HairColor, "#123abc" => true)]
+    #[test_case(Field::HairColor, "#123abz" => false)]
+    #[test_case(Field::HairColor, "123abc" => false)]
+    #[test_case(Field::EyeColor, "brn" => true)]
+    #[test_case(Field::EyeColor, "wat" => false)]
+    #[test_case(Field::PassportId, "000000001" => true)]
+    #[test_case(Field::PassportId, "0123456789" => false)]
+    fn ex4_rule_check(field: Field, value: &str) -> bool {
+        let (_, rule) = RULES.iter().find(|(rule_field, _)| *rule_field == field).unwrap();
+        rule.check(value)
+    }
+
+    #[test]
+    fn ex4_solution_part_two() {
+        assert_eq!(Day4::solution_part_two(INVALID_PASSPORTS).unwrap(), 0);
+        assert_eq!(Day4::solution_part_two(VALID_PASSPORTS).unwrap(), 4);
+        assert_eq!(Day4::solution(INVALID_PASSPORTS).unwrap(), 4);
+    }
+
+    #[test]
+    fn ex4_report() {
+        let lines: Vec<String> = Day4::report(INVALID_PASSPORTS)
+            .unwrap()
+            .iter()
+            .map(|invalid| invalid.to_string())
+            .collect();
+
+        assert_eq!(
+            lines,
+            [
+                "passport at line 1: `eyr:1972` is not year in 2020..=2030",
+                "passport at line 4: `eyr:1967` is not year in 2020..=2030",
+                "passport at line 8: `hcl:dab227` is not `#` followed by 6 of 0-9 or a-f",
+                "passport at line 11: `byr:2007` is not year in 1920..=2002",
+            ]
+        );
+        assert!(Day4::report(VALID_PASSPORTS).unwrap().is_empty());
+    }
+
+    #[test]
+    fn ex4_report_missing_field() {
+        let report = Day4::report("byr:1937 iyr:2017\n\nhgt:183cm").unwrap();
+
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[1].to_string(), "passport at line 3: missing `byr`");
+    }
+
+    #[test]
+    fn ex4_unknown_field() {
+        let err = Day4::solution("byr:1937 iyr:2017\nabc:1").unwrap_err();
+
+        assert_eq!(
+            err.downcast::<Ex4Error>().unwrap(),
+            Ex4Error::InLine {
+                line:   2,
+                source: Box::new(Ex4Error::UnknownField("abc:1".to_owned())),
+            }
+        );
+    }
+
+    #[test]
+    fn ex4_duplicated_field() {
+        let err = parse_passports("byr:1937\nbyr:1938").unwrap_err();
+
+        assert_eq!(
+            err,
+            Ex4Error::InLine {
+                line:   2,
+                source: Box::new(Ex4Error::DuplicatedField("byr".to_owned())),
+            }
+        );
+    }
 }
@@ -6,10 +6,15 @@
 */
 const _MAX_BIT_POS: usize = 9; // "FBFBBFFRLR".len() - 1;
 
+use std::fmt;
+use thiserror::Error;
 
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 
+use super::diagnostic::{Diagnose, Locate};
+use super::part::PartTwo;
+
 pub struct Day5;
 
 impl DaySolver for Day5 {
@@ -18,11 +23,78 @@ impl DaySolver for Day5 {
     const INFO: DayInfo = DayInfo::with_day_and_file("day_5", "data_files/ex5.txt");
 
     fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
-        let res = max_seat_id(_s);
+        let res = max_seat_id(_s).diagnose(_s)?;
         Ok(res)
     }
 }
 
+impl PartTwo for Day5 {
+    type OutputPartTwo = usize;
+
+    const DEFAULT_FILE_PART_TWO: &'static str = "data_files/ex5_part_two.txt";
+
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+        let seat_map = SeatMap::from_passes(s, ROWS).diagnose(s)?;
+        Ok(seat_map.missing_seat()?)
+    }
+}
+
+pub const ROWS: usize = 128;
+pub const COLUMNS: usize = 8;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex5Error {
+    #[error("invalid seat code `{0}` (expected 7 of `F`/`B` followed by 3 of `L`/`R`)")]
+    InvalidSeatCode(String),
+
+    #[error("seat code `{code}` is behind the last of {rows} rows")]
+    SeatOutOfRange { code: String, rows: usize },
+
+    #[error("seat code `{code}` already used in line {first_line}")]
+    DuplicatedPass { code: String, first_line: usize },
+
+    #[error("no free seat between two taken ones")]
+    NoMissingSeat,
+
+    #[error("{0} free seats between two taken ones, expected one")]
+    SeveralMissingSeats(usize),
+
+    #[error("line {line}: {source}")]
+    InLine {
+        line:   usize,
+        source: Box<Ex5Error>,
+    },
+}
+
+impl Locate for Ex5Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex5Error::InLine { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex5Error::InLine { source, .. } => source.fragment(),
+            _ => None,
+        }
+    }
+}
+
+fn validate_seat_code(code: &str) -> Result<(), Ex5Error> {
+    let bytes = code.as_bytes();
+    let is_valid = bytes.len() == 10
+        && bytes[..7].iter().all(|b| *b == b'F' || *b == b'B')
+        && bytes[7..].iter().all(|b| *b == b'L' || *b == b'R');
+
+    if !is_valid {
+        return Err(Ex5Error::InvalidSeatCode(code.to_owned()));
+    }
+
+    Ok(())
+}
+
 fn _extract_seat_num2(code: &str) -> u32 {
     code.bytes()
         .enumerate()
@@ -51,19 +123,96 @@ fn extract_seat_num(input: &str) -> u16 {
     cords.0 * 8 + cords.1
 }
 
-fn max_seat_id(input: &str) -> i32
+fn max_seat_id(input: &str) -> Result<i32, Ex5Error>
 {
-    let mut max_seat_num: i32 = -1;
+    let seat_map = SeatMap::from_passes(input, ROWS)?;
+    Ok(seat_map.max_seat_id().map_or(-1, |seat_id| seat_id as i32))
+}
+
+// -----------------------------------------------------------------------------
+
+/// Taken seats of a plane with `rows` rows of `COLUMNS` seats.
+pub struct SeatMap {
+    rows:  usize,
+    /// Line of the boarding pass of each seat id, `None` for free seats.
+    taken: Vec<Option<usize>>,
+}
+
+impl SeatMap {
+    /// Every pass has to be a valid code of a seat in the plane, used only once.
+    pub fn from_passes(input: &str, rows: usize) -> Result<Self, Ex5Error> {
+        let mut taken: Vec<Option<usize>> = vec![None; rows * COLUMNS];
 
-    for coded_seat_num in input.lines() {
-        let seat_num = i32::from(extract_seat_num(coded_seat_num));
-        println!("{}", &seat_num);
-        if seat_num > max_seat_num {
-            max_seat_num = seat_num;
+        for (line_num, code) in input.lines().enumerate() {
+            let in_line = |err| Ex5Error::InLine {
+                line:   line_num + 1,
+                source: Box::new(err),
+            };
+
+            validate_seat_code(code).map_err(in_line)?;
+
+            let seat_id = usize::from(extract_seat_num(code));
+
+            match taken.get(seat_id) {
+                None => {
+                    return Err(in_line(Ex5Error::SeatOutOfRange {
+                        code: code.to_owned(),
+                        rows,
+                    }));
+                },
+                Some(Some(first_line)) => {
+                    return Err(in_line(Ex5Error::DuplicatedPass {
+                        code:       code.to_owned(),
+                        first_line: *first_line,
+                    }));
+                },
+                Some(None) => taken[seat_id] = Some(line_num + 1),
+            }
         }
+
+        Ok(SeatMap { rows, taken })
+    }
+
+    pub fn is_taken(&self, seat_id: usize) -> bool {
+        self.taken.get(seat_id).is_some_and(|line| line.is_some())
+    }
+
+    pub fn max_seat_id(&self) -> Option<usize> {
+        self.taken.iter().rposition(|line| line.is_some())
     }
 
-    max_seat_num
+    /// The only free seat with both neighbouring ids taken.
+    pub fn missing_seat(&self) -> Result<usize, Ex5Error> {
+        let missing: Vec<usize> = (1..self.taken.len().saturating_sub(1))
+            .filter(|seat_id| {
+                !self.is_taken(*seat_id) && self.is_taken(seat_id - 1) && self.is_taken(seat_id + 1)
+            })
+            .collect();
+
+        match missing[..] {
+            [] => Err(Ex5Error::NoMissingSeat),
+            [seat_id] => Ok(seat_id),
+            _ => Err(Ex5Error::SeveralMissingSeats(missing.len())),
+        }
+    }
+}
+
+/// One line per row, `#` for taken and `.` for free seats, the aisle splits the row in halves.
+impl fmt::Display for SeatMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.rows {
+            let seats: String = (0..COLUMNS)
+                .map(|column| if self.is_taken(row * COLUMNS + column) { '#' } else { '.' })
+                .collect();
+
+            if row > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{:>3} {} {}", row, &seats[..COLUMNS / 2], &seats[COLUMNS / 2..])?;
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -83,7 +232,43 @@ mod tests {
     #[test]
     fn test_max_seat_id(){
         let input = read_to_string("data_files/ex5.txt").unwrap();
-        let res = max_seat_id(&input);
+        let res = max_seat_id(&input).unwrap();
         assert_eq!(res,820) 
     }
+
+    #[test]
+    fn ex5_missing_seat() {
+        let input = read_to_string("data_files/ex5_part_two.txt").unwrap();
+        assert_eq!(Day5::solution_part_two(&input).unwrap(), 611);
+
+        let seat_map = SeatMap::from_passes(&read_to_string("data_files/ex5.txt").unwrap(), ROWS);
+        assert_eq!(seat_map.unwrap().missing_seat(), Err(Ex5Error::NoMissingSeat));
+    }
+
+    #[test_case("FFFFFFFLLL\nFFFFFFFLRL\nFFFFFFFRLL" => Err(Ex5Error::SeveralMissingSeats(2)); "two gaps")]
+    #[test_case("FFFFFFFLLL\nFFFFFFFLRL" => Ok(1); "gap at the front")]
+    fn ex5_missing_seat_gaps(input: &str) -> Result<usize, Ex5Error> {
+        SeatMap::from_passes(input, ROWS).unwrap().missing_seat()
+    }
+
+    #[test_case("BFFFBBFRRR\nFFFBBBFRRR\nBFFFBBFRRR" => Ex5Error::InLine { line: 3, source: Box::new(Ex5Error::DuplicatedPass { code: "BFFFBBFRRR".to_owned(), first_line: 1 }) }; "duplicated")]
+    #[test_case("FFFBBBFRRR\nBBFFBBFRLL" => Ex5Error::InLine { line: 2, source: Box::new(Ex5Error::SeatOutOfRange { code: "BBFFBBFRLL".to_owned(), rows: 100 }) }; "out of range")]
+    fn ex5_seat_map_error(input: &str) -> Ex5Error {
+        SeatMap::from_passes(input, 100).err().unwrap()
+    }
+
+    #[test]
+    fn ex5_seat_map_display() {
+        let seat_map = SeatMap::from_passes("FFFFFFFLLL\nFFFFFFFRRR\nFFFFFFBLRL", 2).unwrap();
+        assert_eq!(seat_map.to_string(), "  0 #... ...#\n  1 ..#. ....");
+        assert_eq!(SeatMap::from_passes("", ROWS).unwrap().to_string().lines().count(), ROWS);
+    }
+
+    #[test_case("BFFFBBFRRR" => Ok(()); "valid")]
+    #[test_case("BFFFBBFRR" => Err(Ex5Error::InvalidSeatCode("BFFFBBFRR".to_owned())); "too short")]
+    #[test_case("BFFFBBLRRR" => Err(Ex5Error::InvalidSeatCode("BFFFBBLRRR".to_owned())); "column letter in row")]
+    #[test_case("BFFFBBFRRĄ" => Err(Ex5Error::InvalidSeatCode("BFFFBBFRRĄ".to_owned())); "non ascii")]
+    fn ex5_validate_seat_code(s: &str) -> Result<(), Ex5Error> {
+        validate_seat_code(s)
+    }
 }
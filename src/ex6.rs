@@ -1,8 +1,12 @@
 use std::collections::HashSet;
+use thiserror::Error;
 
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 
+use super::diagnostic::{Diagnose, Locate};
+use super::part::PartTwo;
+
 pub struct Day6;
 
 impl DaySolver for Day6 {
@@ -11,13 +15,65 @@ impl DaySolver for Day6 {
     const INFO: DayInfo = DayInfo::with_day_and_file("day_6", "data_files/ex6.txt");
 
     fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
-        let res = count_answers2(_s)?;
+        let res = count_answers2(_s, Aggregation::Anyone)?;
         Ok(res)
     }
 }
 
-fn mapp_char(c: char) -> u16 {
-    match c {
+impl PartTwo for Day6 {
+    type OutputPartTwo = usize;
+
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+        let res = count_answers2(s, Aggregation::Everyone)?;
+        Ok(res)
+    }
+}
+
+/// How answers of people from one group are combined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Aggregation {
+    /// Questions anyone in the group answered "yes" to, part one.
+    Anyone,
+    /// Questions everyone in the group answered "yes" to, part two.
+    Everyone,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex6Error {
+    #[error("line {line}: answers have to be lowercase letters, found `{text}`")]
+    InvalidAnswer { line: usize, text: String },
+}
+
+impl Locate for Ex6Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex6Error::InvalidAnswer { line, .. } => Some(*line),
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex6Error::InvalidAnswer { text, .. } => text
+                .chars()
+                .find(|c| !c.is_ascii_lowercase())
+                .map(String::from),
+        }
+    }
+}
+
+fn validate_line(line_num: usize, line: &str) -> Result<(), Ex6Error> {
+    if line.chars().any(|c| !c.is_ascii_lowercase()) {
+        return Err(Ex6Error::InvalidAnswer {
+            line: line_num + 1,
+            text: line.to_owned(),
+        });
+    }
+
+    Ok(())
+}
+
+fn mapp_char(c: char) -> Option<u16> {
+    let prime = match c {
         'a' => 2,
         'b' => 3,
         'c' => 5,
@@ -44,165 +100,232 @@ fn mapp_char(c: char) -> u16 {
         'x' => 89,
         'y' => 97,
         'z' => 101,
-        _ => unreachable!(),
+        _ => return None,
+    };
+
+    Some(prime)
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
     }
 }
 
-pub fn count_answers4(pattern: &str) -> u32 {
+/// Product of primes is a set of answers, union is their lcm and intersection their gcd.
+fn combine_mappers(aggregation: Aggregation, group: u128, person: u128) -> u128 {
+    match aggregation {
+        Aggregation::Anyone => group / gcd(group, person) * person,
+        Aggregation::Everyone => gcd(group, person),
+    }
+}
+
+fn count_mapped(mapper: u128) -> usize {
+    ('a'..='z')
+        .filter_map(mapp_char)
+        .filter(|prime| mapper.is_multiple_of(u128::from(*prime)))
+        .count()
+}
+
+fn combine_sets(
+    aggregation: Aggregation,
+    mut group: HashSet<char>,
+    person: HashSet<char>,
+) -> HashSet<char> {
+    match aggregation {
+        Aggregation::Anyone => group.extend(person),
+        Aggregation::Everyone => group.retain(|question| person.contains(question)),
+    }
+
+    group
+}
+
+/// Answers of every person sorted and deduplicated, then concatenated.
+fn count_sorted(questions: &mut [char], people: usize, aggregation: Aggregation) -> usize {
+    questions.sort_unstable();
+    questions
+        .chunk_by(|a, b| a == b)
+        .filter(|same| aggregation == Aggregation::Anyone || same.len() == people)
+        .count()
+}
+
+pub fn count_answers4(pattern: &str, aggregation: Aggregation) -> u32 {
     pattern
         .split("\n\n")
         .map(|group| {
             group
-                .bytes()
-                .filter(|e| *e != b'\n')
-                .fold(0u32, |answers, b| answers | 1 << (b - b'a'))
+                .lines()
+                .map(|person| person.trim())
+                .filter(|person| !person.is_empty())
+                .map(|person| person.bytes().fold(0u32, |answers, b| answers | 1 << (b - b'a')))
+                .reduce(|group, person| match aggregation {
+                    Aggregation::Anyone => group | person,
+                    Aggregation::Everyone => group & person,
+                })
+                .unwrap_or(0)
                 .count_ones()
         })
         .sum::<u32>()
 }
 
-pub fn count_answers3_bench(pattern: &str) -> usize {
-    let mut mapper = 1;
+pub fn count_answers3_bench(pattern: &str, aggregation: Aggregation) -> usize {
+    let mut mapper: Option<u128> = None;
     let mut counter: usize = 0;
-    let mut questions: usize = 0;
 
     for line in pattern.split('\n').map(|l| l.trim()) {
-        let line = line;
         if line.is_empty() {
-            counter += questions;
-            mapper = 1;
-            questions = 0;
+            counter += mapper.take().map_or(0, count_mapped);
         } else {
+            let mut person: u128 = 1;
             for question in line.chars() {
-                let to_check = mapp_char(question) as u128;
-                if mapper % to_check != 0 {
-                    mapper *= to_check;
-                    questions += 1;
+                let Some(to_check) = mapp_char(question) else {
+                    continue;
+                };
+                let to_check = to_check as u128;
+                if !person.is_multiple_of(to_check) {
+                    person *= to_check;
                 }
             }
+
+            mapper = Some(match mapper {
+                None => person,
+                Some(group) => combine_mappers(aggregation, group, person),
+            });
         }
     }
 
-    counter += questions;
+    counter += mapper.map_or(0, count_mapped);
 
     counter
 }
 
-pub fn count_answers3(input: &str) -> anyhow::Result<usize>
+pub fn count_answers3(input: &str, aggregation: Aggregation) -> anyhow::Result<usize>
 {
-    let mut mapper = 1;
+    let mut mapper: Option<u128> = None;
     let mut counter: usize = 0;
-    let mut questions: usize = 0;
 
-    for line in input.lines() {
+    for (line_num, line) in input.lines().enumerate() {
+        validate_line(line_num, line).diagnose(input)?;
+
         if line.is_empty() {
-            counter += questions;
-            mapper = 1;
-            questions = 0;
+            counter += mapper.take().map_or(0, count_mapped);
         } else {
+            let mut person: u128 = 1;
             for question in line.chars() {
-                let to_check = mapp_char(question) as u128;
-                if mapper % to_check != 0 {
-                    mapper *= to_check;
-                    questions += 1;
+                let to_check = mapp_char(question).unwrap_or(1) as u128;
+                if !person.is_multiple_of(to_check) {
+                    person *= to_check;
                 }
             }
+
+            mapper = Some(match mapper {
+                None => person,
+                Some(group) => combine_mappers(aggregation, group, person),
+            });
         }
     }
 
-    counter += questions;
+    counter += mapper.map_or(0, count_mapped);
 
     Ok(counter)
 }
 
-pub fn count_answers2_bench(pattern: &str) -> usize {
+pub fn count_answers2_bench(pattern: &str, aggregation: Aggregation) -> usize {
     let mut questions: Vec<char> = Vec::new();
+    let mut people: usize = 0;
     let mut counter: usize = 0;
 
     for line in pattern.split('\n').map(|l| l.trim()) {
-        let line = line;
         if line.is_empty() {
-            questions.sort_unstable();
-            questions.dedup();
-            counter += questions.len();
+            counter += count_sorted(&mut questions, people, aggregation);
             questions = Vec::new();
+            people = 0;
         } else {
-            for question in line.chars() {
-                questions.push(question);
-            }
+            let mut person: Vec<char> = line.chars().collect();
+            person.sort_unstable();
+            person.dedup();
+            questions.extend(person);
+            people += 1;
         }
     }
 
-    questions.sort_unstable();
-    questions.dedup();
-    counter += questions.len();
+    counter += count_sorted(&mut questions, people, aggregation);
 
     counter
 }
 
-pub fn count_answers2(input: &str) -> anyhow::Result<usize>
+pub fn count_answers2(input: &str, aggregation: Aggregation) -> anyhow::Result<usize>
 {
     let mut questions: Vec<char> = Vec::new();
+    let mut people: usize = 0;
     let mut counter: usize = 0;
 
-    for line in input.lines() {
+    for (line_num, line) in input.lines().enumerate() {
+        validate_line(line_num, line).diagnose(input)?;
+
         if line.is_empty() {
-            questions.sort_unstable();
-            questions.dedup();
-            counter += questions.len();
+            counter += count_sorted(&mut questions, people, aggregation);
             questions = Vec::new();
+            people = 0;
         } else {
-            for question in line.chars() {
-                questions.push(question);
-            }
+            let mut person: Vec<char> = line.chars().collect();
+            person.sort_unstable();
+            person.dedup();
+            questions.extend(person);
+            people += 1;
         }
     }
 
-    questions.sort_unstable();
-    questions.dedup();
-    counter += questions.len();
+    counter += count_sorted(&mut questions, people, aggregation);
 
     Ok(counter)
 }
 
-pub fn count_answers_bench(pattern: &str) -> usize {
-    let mut questions: HashSet<char> = HashSet::new();
+pub fn count_answers_bench(pattern: &str, aggregation: Aggregation) -> usize {
+    let mut questions: Option<HashSet<char>> = None;
     let mut counter: usize = 0;
 
     for line in pattern.split('\n').map(|l| l.trim()) {
-        let line = line;
         if line.is_empty() {
-            counter += questions.len();
-            questions = HashSet::new();
+            counter += questions.take().map_or(0, |group| group.len());
         } else {
-            for question in line.chars() {
-                questions.insert(question);
-            }
+            let person: HashSet<char> = line.chars().collect();
+
+            questions = Some(match questions.take() {
+                None => person,
+                Some(group) => combine_sets(aggregation, group, person),
+            });
         }
     }
 
-    counter += questions.len();
+    counter += questions.map_or(0, |group| group.len());
 
     counter
 }
 
-pub fn count_answers(input: &str) -> anyhow::Result<usize>
+pub fn count_answers(input: &str, aggregation: Aggregation) -> anyhow::Result<usize>
 {
-    let mut questions: HashSet<char> = HashSet::new();
+    let mut questions: Option<HashSet<char>> = None;
     let mut counter: usize = 0;
 
-    for line in input.lines() {
+    for (line_num, line) in input.lines().enumerate() {
+        validate_line(line_num, line).diagnose(input)?;
+
         if line.is_empty() {
-            counter += questions.len();
-            questions = HashSet::new();
+            counter += questions.take().map_or(0, |group| group.len());
         } else {
-            for question in line.chars() {
-                questions.insert(question);
-            }
+            let person: HashSet<char> = line.chars().collect();
+
+            questions = Some(match questions.take() {
+                None => person,
+                Some(group) => combine_sets(aggregation, group, person),
+            });
         }
     }
 
-    counter += questions.len();
+    counter += questions.map_or(0, |group| group.len());
 
     Ok(counter)
 }
@@ -211,10 +334,55 @@ pub fn count_answers(input: &str) -> anyhow::Result<usize>
 mod tests {
     use super::*;
     use aoc_utils::read_to_string;
+    use test_case::test_case;
 
     #[test]
     fn test_count_answers() {
         let input = read_to_string("data_files/ex6.txt").unwrap();
-        assert_eq!(count_answers(&input).unwrap(), 11)
+        assert_eq!(count_answers(&input, Aggregation::Anyone).unwrap(), 11)
+    }
+
+    #[test_case(Aggregation::Anyone => (11, 11, 11, 11))]
+    #[test_case(Aggregation::Everyone => (6, 6, 6, 6))]
+    fn test_count_answers_variants(aggregation: Aggregation) -> (usize, usize, usize, usize) {
+        let input = read_to_string("data_files/ex6.txt").unwrap();
+
+        (
+            count_answers(&input, aggregation).unwrap(),
+            count_answers2(&input, aggregation).unwrap(),
+            count_answers3(&input, aggregation).unwrap(),
+            count_answers4(&input, aggregation) as usize,
+        )
+    }
+
+    #[test_case(Aggregation::Anyone => (8, 8, 8))]
+    #[test_case(Aggregation::Everyone => (5, 5, 5))]
+    fn test_count_answers_bench_variants(aggregation: Aggregation) -> (usize, usize, usize) {
+        let input = "abc\n  \n  ab\n  ba\n\n  abb\n  c";
+
+        (
+            count_answers_bench(input, aggregation),
+            count_answers2_bench(input, aggregation),
+            count_answers3_bench(input, aggregation),
+        )
+    }
+
+    #[test]
+    fn test_solution_part_two() {
+        assert_eq!(Day6::solve_default_file().unwrap(), 11);
+        assert_eq!(Day6::solution_part_two("abc\n\na\nb\nc\n\nab\nac").unwrap(), 4);
+    }
+
+    #[test]
+    fn test_count_answers_invalid() {
+        let err = count_answers2("abc\n\na1", Aggregation::Everyone).unwrap_err();
+
+        assert_eq!(
+            err.downcast::<Ex6Error>().unwrap(),
+            Ex6Error::InvalidAnswer {
+                line: 3,
+                text: "a1".to_owned(),
+            }
+        );
     }
 }
@@ -1,9 +1,19 @@
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 use fnv::FnvHashMap as HashMap;
+use fnv::FnvHashSet as HashSet;
 use lazy_regex::{regex, Lazy, Regex};
-use std::collections::VecDeque;
+use petgraph::algo::{has_path_connecting, toposort};
+use petgraph::dot::Dot;
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::{Bfs, EdgeRef, Reversed};
 use std::str::FromStr;
+use thiserror::Error;
+
+use super::diagnostic::{Diagnose, Locate};
+use super::part::PartTwo;
+
+pub const TARGET: &str = "shiny gold";
 
 pub struct Day7;
 
@@ -14,71 +24,223 @@ impl DaySolver for Day7 {
         DayInfo::with_day_and_file_and_variant("day_7", "data_files/ex7.txt", "base");
 
     fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
-        let rules = _s.parse::<Rules>()?;
-        let res = rules
-            .get_bags()
-            .filter(|bag| rules.contains_shiny_gold(bag))
-            .count();
+        let rules = _s.parse::<Rules>().diagnose(_s)?;
+        Ok(rules.containers(TARGET)?)
+    }
+}
+
+impl PartTwo for Day7 {
+    type OutputPartTwo = u64;
+
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+        let rules = s.parse::<Rules>().diagnose(s)?;
+        Ok(rules.nested_count(TARGET)?)
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex7Error {
+    #[error("line {line}: expected `<colour> bags contain <contents>`, found `{text}`")]
+    MissingContain { line: usize, text: String },
+
+    #[error("line {line}: unable to parse bag contents `{text}`")]
+    InvalidContents { line: usize, text: String },
 
-        Ok(res)
+    #[error("bag `{0}` has no rule")]
+    UnknownBag(String),
+
+    #[error("bags contain themselves: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+
+    #[error("number of bags inside of `{0}` does not fit in u64")]
+    CountOverflow(String),
+}
+
+impl Locate for Ex7Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex7Error::MissingContain { line, .. } | Ex7Error::InvalidContents { line, .. } => {
+                Some(*line)
+            },
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex7Error::MissingContain { text, .. } | Ex7Error::InvalidContents { text, .. } => {
+                Some(text.to_owned())
+            },
+            _ => None,
+        }
     }
 }
 
-struct Rules {
-    body: HashMap<String, VecDeque<String>>,
+/// Bags are nodes, an edge leads from a bag to each bag it contains, weighted by their number.
+pub struct Rules {
+    graph: DiGraph<String, u32>,
+    nodes: HashMap<String, NodeIndex>,
 }
 
 impl FromStr for Rules {
-    type Err = anyhow::Error;
+    type Err = Ex7Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let re: &Lazy<Regex> = regex!(r"(\d+) (?P<color>\w+ \w+) bags?");
+        let re: &Lazy<Regex> = regex!(r"(?P<count>\d+) (?P<color>\w+ \w+) bags?");
 
-        let body: HashMap<String, VecDeque<String>> = s
-            .lines()
-            .map(|line| {
-                let (bag, contained_bags) = line.split_once(" bags contain ").unwrap();
+        let mut rules = Rules {
+            graph: DiGraph::new(),
+            nodes: HashMap::default(),
+        };
+        let mut defined: HashSet<NodeIndex> = HashSet::default();
 
-                let mut value: VecDeque<String> = VecDeque::new();
+        for (line_num, line) in s.lines().enumerate() {
+            let (bag, contained_bags) = line
+                .split_once(" bags contain ")
+                .ok_or_else(|| Ex7Error::MissingContain {
+                    line: line_num + 1,
+                    text: line.to_owned(),
+                })?;
 
+            let bag = rules.node(bag);
+            defined.insert(bag);
+
+            if contained_bags != "no other bags." {
                 for i in contained_bags.split(", ") {
-                    match re.captures(i) {
-                        None => {
-                            break;
-                        },
-                        Some(cap) => {
-                            value.push_back(cap["color"].to_string());
-                        },
-                    }
+                    let invalid = || Ex7Error::InvalidContents {
+                        line: line_num + 1,
+                        text: i.to_owned(),
+                    };
+
+                    let cap = re.captures(i).ok_or_else(invalid)?;
+                    let count = cap["count"].parse::<u32>().map_err(|_| invalid())?;
+                    let inner = rules.node(&cap["color"]);
+
+                    rules.graph.add_edge(bag, inner, count);
                 }
+            }
+        }
+
+        if let Some(undefined) = rules.graph.node_indices().find(|node| !defined.contains(node)) {
+            return Err(Ex7Error::UnknownBag(rules.graph[undefined].to_owned()));
+        }
 
-                (bag.to_owned(), value)
-            })
-            .collect();
+        if let Err(cycle) = toposort(&rules.graph, None) {
+            return Err(Ex7Error::Cycle(rules.cycle_through(cycle.node_id())));
+        }
 
-        Ok(Rules { body })
+        Ok(rules)
     }
 }
 
 impl Rules {
-    fn contains_shiny_gold(&self, bag: &String) -> bool {
-        let mut queue = self.body.get(bag).unwrap().to_owned();
+    fn node(&mut self, bag: &str) -> NodeIndex {
+        if let Some(node) = self.nodes.get(bag) {
+            return *node;
+        }
 
-        while !queue.is_empty() {
-            if let Some(checked) = queue.pop_front() {
-                if checked == "shiny gold" {
-                    return true;
-                }
+        let node = self.graph.add_node(bag.to_owned());
+        self.nodes.insert(bag.to_owned(), node);
+        node
+    }
 
-                queue.append(&mut self.body.get(&checked).unwrap().to_owned());
+    fn find(&self, bag: &str) -> Result<NodeIndex, Ex7Error> {
+        self.nodes
+            .get(bag)
+            .copied()
+            .ok_or_else(|| Ex7Error::UnknownBag(bag.to_owned()))
+    }
+
+    /// Bags on the shortest way from `start` back to itself.
+    fn cycle_through(&self, start: NodeIndex) -> Vec<String> {
+        let mut previous: HashMap<NodeIndex, NodeIndex> = HashMap::default();
+        let mut bfs = Bfs::new(&self.graph, start);
+
+        'search: while let Some(node) = bfs.next(&self.graph) {
+            for inner in self.graph.neighbors(node) {
+                previous.entry(inner).or_insert(node);
+
+                if inner == start {
+                    break 'search;
+                }
             }
         }
 
-        false
+        let mut cycle = vec![start];
+        let mut node = start;
+
+        while let Some(&outer) = previous.get(&node).filter(|outer| **outer != start) {
+            node = outer;
+            cycle.push(node);
+        }
+
+        // Start from the bag seen first, so the reported cycle does not depend on the search
+        cycle.reverse();
+        let first = cycle.iter().enumerate().min_by_key(|(_, node)| **node).unwrap().0;
+        cycle.rotate_left(first);
+        cycle.push(cycle[0]);
+
+        cycle.into_iter().map(|node| self.graph[node].to_owned()).collect()
     }
 
-    fn get_bags(&self) -> std::collections::hash_map::Keys<'_, String, VecDeque<String>> {
-        self.body.keys()
+    /// Whether `bag` holds a `target` bag at any depth.
+    pub fn contains(&self, bag: &str, target: &str) -> Result<bool, Ex7Error> {
+        let target = self.find(target)?;
+
+        Ok(self
+            .graph
+            .neighbors(self.find(bag)?)
+            .any(|inner| has_path_connecting(&self.graph, inner, target, None)))
+    }
+
+    /// Number of different bags which eventually hold a `target` bag.
+    pub fn containers(&self, target: &str) -> Result<usize, Ex7Error> {
+        let reversed = Reversed(&self.graph);
+        let mut bfs = Bfs::new(reversed, self.find(target)?);
+        let mut res = 0;
+
+        while bfs.next(reversed).is_some() {
+            res += 1;
+        }
+
+        Ok(res - 1)
+    }
+
+    /// Number of bags inside of a single `bag`.
+    pub fn nested_count(&self, bag: &str) -> Result<u64, Ex7Error> {
+        let mut counts: HashMap<NodeIndex, u64> = HashMap::default();
+        self.nested_count_of(self.find(bag)?, &mut counts)
+    }
+
+    fn nested_count_of(
+        &self,
+        bag: NodeIndex,
+        counts: &mut HashMap<NodeIndex, u64>,
+    ) -> Result<u64, Ex7Error> {
+        if let Some(count) = counts.get(&bag) {
+            return Ok(*count);
+        }
+
+        let overflow = || Ex7Error::CountOverflow(self.graph[bag].to_owned());
+        let mut count: u64 = 0;
+
+        for edge in self.graph.edges(bag) {
+            let inner = self.nested_count_of(edge.target(), counts)?;
+            let bags = inner
+                .checked_add(1)
+                .and_then(|bags| bags.checked_mul(u64::from(*edge.weight())))
+                .ok_or_else(overflow)?;
+
+            count = count.checked_add(bags).ok_or_else(overflow)?;
+        }
+
+        counts.insert(bag, count);
+        Ok(count)
+    }
+
+    /// Rules in graphviz DOT format, edges are labeled with the number of inner bags.
+    pub fn to_dot(&self) -> String {
+        format!("{}", Dot::new(&self.graph))
     }
 }
 
@@ -99,7 +261,70 @@ mod tests {
         input
             .parse::<Rules>()
             .unwrap()
-            .contains_shiny_gold(&bag.to_string())
+            .contains(bag, TARGET)
+            .unwrap()
+    }
+
+    #[test_case("light red bags contain 1 bright white bag." => Err(Ex7Error::UnknownBag("bright white".to_owned())); "unknown bag")]
+    #[test_case("light red bags contain\nx" => Err(Ex7Error::MissingContain{line: 1, text: "light red bags contain".to_owned()}); "missing contain")]
+    #[test_case("light red bags contain one bright white bag." => Err(Ex7Error::InvalidContents{line: 1, text: "one bright white bag.".to_owned()}); "invalid contents")]
+    #[test_case("light red bags contain 1 shiny gold bag.\nshiny gold bags contain 2 light red bags." => Err(Ex7Error::Cycle(vec!["light red".to_owned(), "shiny gold".to_owned(), "light red".to_owned()])); "cycle")]
+    #[test_case("light red bags contain 1 light red bag." => Err(Ex7Error::Cycle(vec!["light red".to_owned(), "light red".to_owned()])); "bag in itself")]
+    fn ex7_errors(s: &str) -> Result<bool, Ex7Error> {
+        s.parse::<Rules>()?.contains("light red", TARGET)
+    }
+
+    #[test_case(TARGET => 4)]
+    #[test_case("bright white" => 2)]
+    #[test_case("light red" => 0)]
+    fn ex7_containers(target: &str) -> usize {
+        let input = read_to_string("data_files/ex7.txt").unwrap();
+        input.parse::<Rules>().unwrap().containers(target).unwrap()
+    }
+
+    #[test_case(TARGET => 32)]
+    #[test_case("dark olive" => 7)]
+    #[test_case("faded blue" => 0)]
+    fn ex7_nested_count(bag: &str) -> u64 {
+        let input = read_to_string("data_files/ex7.txt").unwrap();
+        input.parse::<Rules>().unwrap().nested_count(bag).unwrap()
+    }
+
+    #[test]
+    fn ex7_solution_part_two() {
+        let input = "shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain no other bags.";
+
+        assert_eq!(Day7::solution_part_two(input).unwrap(), 14);
+    }
+
+    #[test]
+    fn ex7_nested_count_overflow() {
+        let input = "light red bags contain 4000000000 bright white bags.
+bright white bags contain 4000000000 faded blue bags.
+faded blue bags contain 4000000000 dark olive bags.
+dark olive bags contain no other bags.";
+        let rules = input.parse::<Rules>().unwrap();
+
+        assert_eq!(rules.nested_count("bright white"), Ok(16000000004000000000));
+        assert_eq!(
+            rules.nested_count("light red"),
+            Err(Ex7Error::CountOverflow("light red".to_owned()))
+        );
+    }
+
+    #[test]
+    fn ex7_to_dot() {
+        let input = "light red bags contain 2 faded blue bags.
+faded blue bags contain no other bags.";
+        let rules = input.parse::<Rules>().unwrap();
+        let dot = rules.to_dot();
+
+        assert!(dot.starts_with("digraph {"));
+        assert!(dot.contains("[ label = \"light red\" ]"));
+        assert!(dot.contains("0 -> 1 [ label = \"2\" ]"));
     }
 
     #[test]
@@ -1,10 +1,12 @@
-use std::collections::HashMap;
 use std::str::FromStr;
 use thiserror::Error;
 
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 
+use super::diagnostic::{Diagnose, Locate};
+use super::part::PartTwo;
+
 pub struct Day8;
 
 impl DaySolver for Day8 {
@@ -18,6 +20,15 @@ impl DaySolver for Day8 {
     }
 }
 
+impl PartTwo for Day8 {
+    type OutputPartTwo = i16;
+
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+        let program = load_instructions(s)?;
+        Ok(repair(&program)?.accumulator)
+    }
+}
+
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum OperationError {
     #[error("unable to parse operation")]
@@ -31,11 +42,33 @@ pub enum OperationError {
 
     #[error("unable to find operation with given number")]
     NoOperation,
+
+    #[error("line {line}: jump out of the program to instruction {pc}")]
+    JumpOutOfBounds { line: usize, pc: isize },
+
+    #[error("no single `jmp`/`nop` change makes the program terminate")]
+    NoRepair,
+}
+
+impl Locate for OperationError {
+    fn line(&self) -> Option<usize> {
+        match self {
+            OperationError::JumpOutOfBounds { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            OperationError::UnknownOperation(operation) => Some(operation.to_owned()),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Debug, PartialEq)]
-enum Operation {
-    Nop,
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operation {
+    Nop(i16),
     Acc(i16),
     Jmp(i16),
 }
@@ -51,7 +84,7 @@ impl FromStr for Operation {
         let argument = i16::from_str(argument).map_err(|_| OperationError::ParseArgumentError)?;
 
         let operation = match operation {
-            "nop" => Operation::Nop,
+            "nop" => Operation::Nop(argument),
             "acc" => Operation::Acc(argument),
             "jmp" => Operation::Jmp(argument),
             _ => return Err(OperationError::UnknownOperation(operation.into())),
@@ -72,17 +105,205 @@ fn _load_instruction_num(input: &str, num: usize) -> anyhow::Result<String>
     Err(anyhow::anyhow!("{}", OperationError::NoOperation))
 }
 
-fn load_instructions(input: &str) -> anyhow::Result<HashMap<usize, Operation>>
+pub fn load_instructions(input: &str) -> anyhow::Result<Vec<Operation>>
 {
-    let mut operations: HashMap<usize, Operation> = HashMap::new();
+    let mut operations: Vec<Operation> = Vec::new();
 
     for (line_num, line) in input.lines().enumerate() {
-        operations.insert(line_num, Operation::from_str(line)?);
+        let operation = Operation::from_str(line).diagnose_line(input, line_num + 1)?;
+        operations.push(operation);
     }
 
     Ok(operations)
 }
 
+impl Operation {
+    /// Offset of the next instruction to execute.
+    fn offset(&self) -> isize {
+        match self {
+            Operation::Jmp(num) => *num as isize,
+            _ => 1,
+        }
+    }
+
+    /// `jmp` turned into `nop` and the other way around, `acc` can't be repaired.
+    fn flipped(&self) -> Option<Operation> {
+        match self {
+            Operation::Nop(num) => Some(Operation::Jmp(*num)),
+            Operation::Jmp(num) => Some(Operation::Nop(*num)),
+            Operation::Acc(_) => None,
+        }
+    }
+}
+
+/// Why the program stopped, along with the accumulator at that moment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Termination {
+    /// Instruction `pc` was about to be executed a second time.
+    Loop { pc: usize, accumulator: i16 },
+    /// Execution continued right after the last instruction.
+    Terminated { accumulator: i16 },
+    /// Jump from instruction `from` to `pc`, which is neither an instruction nor the end.
+    OutOfBounds { from: usize, pc: isize, accumulator: i16 },
+}
+
+impl Termination {
+    pub fn accumulator(&self) -> i16 {
+        match self {
+            Termination::Loop { accumulator, .. }
+            | Termination::Terminated { accumulator }
+            | Termination::OutOfBounds { accumulator, .. } => *accumulator,
+        }
+    }
+}
+
+/// Handheld boot code machine.
+pub struct Vm<'a> {
+    program:     &'a [Operation],
+    pc:          isize,
+    last_pc:     usize,
+    accumulator: i16,
+}
+
+impl<'a> Vm<'a> {
+    pub fn new(program: &'a [Operation]) -> Self {
+        Vm {
+            program,
+            pc: 0,
+            last_pc: 0,
+            accumulator: 0,
+        }
+    }
+
+    pub fn pc(&self) -> isize {
+        self.pc
+    }
+
+    pub fn accumulator(&self) -> i16 {
+        self.accumulator
+    }
+
+    /// Executes a single instruction, `Some` once the program can't continue.
+    pub fn step(&mut self) -> Option<Termination> {
+        if let Some(termination) = self.outside() {
+            return Some(termination);
+        }
+
+        self.last_pc = self.pc as usize;
+        let operation = self.program[self.last_pc];
+
+        if let Operation::Acc(value) = operation {
+            self.accumulator += value;
+        }
+        self.pc += operation.offset();
+
+        self.outside()
+    }
+
+    /// Runs until the program leaves its instructions or starts repeating itself.
+    pub fn run(&mut self) -> Termination {
+        let mut visited = vec![false; self.program.len()];
+
+        loop {
+            if let Some(termination) = self.outside() {
+                return termination;
+            }
+
+            let pc = self.pc as usize;
+            if visited[pc] {
+                return Termination::Loop {
+                    pc,
+                    accumulator: self.accumulator,
+                };
+            }
+            visited[pc] = true;
+
+            self.step();
+        }
+    }
+
+    fn outside(&self) -> Option<Termination> {
+        let accumulator = self.accumulator;
+        let end = self.program.len() as isize;
+
+        match self.pc {
+            pc if pc == end => Some(Termination::Terminated { accumulator }),
+            pc if pc < 0 || pc > end => Some(Termination::OutOfBounds {
+                from: self.last_pc,
+                pc,
+                accumulator,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Repair {
+    /// Index of the flipped instruction.
+    pub pc:          usize,
+    pub accumulator: i16,
+}
+
+/// Instructions from which execution eventually continues right after the last one.
+fn terminating(program: &[Operation]) -> Vec<bool> {
+    let end = program.len();
+    let mut sources: Vec<Vec<usize>> = vec![Vec::new(); end + 1];
+
+    for (pc, operation) in program.iter().enumerate() {
+        let next = pc as isize + operation.offset();
+        if (0..=end as isize).contains(&next) {
+            sources[next as usize].push(pc);
+        }
+    }
+
+    let mut res = vec![false; end + 1];
+    let mut queue = vec![end];
+    res[end] = true;
+
+    while let Some(pc) = queue.pop() {
+        for source in &sources[pc] {
+            if !res[*source] {
+                res[*source] = true;
+                queue.push(*source);
+            }
+        }
+    }
+
+    res
+}
+
+/// Flips the first executed `jmp`/`nop` leading to an instruction from which the end
+/// is reachable, so every instruction is looked at only a few times.
+pub fn repair(program: &[Operation]) -> Result<Repair, OperationError> {
+    let terminating = terminating(program);
+    let mut visited = vec![false; program.len()];
+    let mut pc: isize = 0;
+
+    while (0..program.len() as isize).contains(&pc) && !visited[pc as usize] {
+        let index = pc as usize;
+        visited[index] = true;
+
+        if let Some(flipped) = program[index].flipped() {
+            let next = pc + flipped.offset();
+
+            if (0..=program.len() as isize).contains(&next) && terminating[next as usize] {
+                let mut repaired = program.to_vec();
+                repaired[index] = flipped;
+
+                return Ok(Repair {
+                    pc:          index,
+                    accumulator: Vm::new(&repaired).run().accumulator(),
+                });
+            }
+        }
+
+        pc += program[index].offset();
+    }
+
+    Err(OperationError::NoRepair)
+}
+
 fn _evaluate2(input: &str) -> anyhow::Result<i16>
 {
     let mut accumulator: i16 = 0;
@@ -96,7 +317,7 @@ fn _evaluate2(input: &str) -> anyhow::Result<i16>
         visited.push(operation_num);
 
         match op {
-            Operation::Nop => {
+            Operation::Nop(_) => {
                 operation_num += 1;
             },
             Operation::Acc(value) => {
@@ -116,30 +337,13 @@ fn _evaluate2(input: &str) -> anyhow::Result<i16>
 fn evaluate1(input: &str) -> anyhow::Result<i16>
 {
     let operations = load_instructions(input)?;
-    let mut accumulator: i16 = 0;
-    let mut visited: Vec<usize> = Vec::new();
-
-    let mut operation_num: usize = 0;
 
-    while !visited.contains(&operation_num) {
-        visited.push(operation_num);
-        let op = &operations[&operation_num];
-        match op {
-            Operation::Nop => {
-                operation_num += 1;
-            },
-            Operation::Acc(value) => {
-                operation_num += 1;
-                accumulator += value;
-            },
-
-            Operation::Jmp(num) => {
-                operation_num = (operation_num as i16 + num) as usize;
-            },
-        }
+    match Vm::new(&operations).run() {
+        Termination::OutOfBounds { from, pc, .. } => {
+            Err(OperationError::JumpOutOfBounds { line: from + 1, pc }).diagnose(input)
+        },
+        termination => Ok(termination.accumulator()),
     }
-
-    Ok(accumulator)
 }
 
 #[cfg(test)]
@@ -148,7 +352,7 @@ mod tests {
     use test_case::test_case;
     use aoc_utils::read_to_string;
 
-    #[test_case("nop +0" => Ok(Operation::Nop); "nop +0 ok")]
+    #[test_case("nop +0" => Ok(Operation::Nop(0)); "nop +0 ok")]
     #[test_case("acc +1" => Ok(Operation::Acc(1)); "acc +1 ok")]
     #[test_case("jmp +4" => Ok(Operation::Jmp(4)); "jmp +4 ok")]
     #[test_case("jmp -3" => Ok(Operation::Jmp(-3)); "jmp -3 ok")]
@@ -175,6 +379,51 @@ mod tests {
         assert_eq!(evaluate1(&input).unwrap(), 5);
     }
 
+    #[test]
+    fn test_ex8_evaluate1_out_of_bounds() {
+        let err = evaluate1("acc +1\njmp -5").unwrap_err();
+
+        assert_eq!(
+            err.downcast::<OperationError>().unwrap(),
+            OperationError::JumpOutOfBounds { line: 2, pc: -4 }
+        );
+    }
+
+    #[test_case("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6" => Termination::Loop { pc: 1, accumulator: 5 }; "loop detected")]
+    #[test_case("nop +0\nacc +2" => Termination::Terminated { accumulator: 2 }; "terminated")]
+    #[test_case("acc +1\njmp -5" => Termination::OutOfBounds { from: 1, pc: -4, accumulator: 1 }; "out of bounds")]
+    #[test_case("" => Termination::Terminated { accumulator: 0 }; "empty program")]
+    fn test_ex8_vm_run(input: &str) -> Termination {
+        let program = load_instructions(input).unwrap();
+        Vm::new(&program).run()
+    }
+
+    #[test]
+    fn test_ex8_vm_step() {
+        let program = load_instructions("acc +3\njmp +2\nacc -1\nacc +1").unwrap();
+        let mut vm = Vm::new(&program);
+
+        assert_eq!(vm.step(), None);
+        assert_eq!((vm.pc(), vm.accumulator()), (1, 3));
+        assert_eq!(vm.step(), None);
+        assert_eq!(vm.pc(), 3);
+        assert_eq!(vm.step(), Some(Termination::Terminated { accumulator: 4 }));
+        assert_eq!(vm.step(), Some(Termination::Terminated { accumulator: 4 }));
+    }
+
+    #[test_case("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6" => Ok(Repair { pc: 7, accumulator: 8 }); "jmp to nop")]
+    #[test_case("acc +2\nnop +2\njmp -2" => Ok(Repair { pc: 1, accumulator: 2 }); "nop to jmp")]
+    #[test_case("jmp +0\nacc +1\njmp -1" => Err(OperationError::NoRepair); "no repair")]
+    fn test_ex8_repair(input: &str) -> Result<Repair, OperationError> {
+        repair(&load_instructions(input).unwrap())
+    }
+
+    #[test]
+    fn test_ex8_solution_part_two() {
+        let input = read_to_string("data_files/ex8.txt").unwrap();
+        assert_eq!(Day8::solution_part_two(&input).unwrap(), 8);
+    }
+
     #[test]
     fn test_ex8_evaluate2() {
         let input = read_to_string("data_files/ex8.txt").unwrap();
@@ -1,34 +1,79 @@
+use thiserror::Error;
+
 use aoc_utils::DayInfo;
 use aoc_utils::DaySolver;
 
+use super::diagnostic::{Diagnose, Locate};
+use super::part::PartTwo;
+
+/// Preamble used by `Day9`, as in the example in `data_files/ex9.txt`, real inputs use 25.
+pub const DEFAULT_PREAMBLE: usize = 5;
+
 pub struct Day9;
 
 impl DaySolver for Day9 {
-    type Output = u16;
+    type Output = u64;
 
     const INFO: DayInfo = DayInfo::with_day_and_file("day_9", "data_files/ex9.txt");
 
     fn solution(_s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
-        match solve(_s, 5) {
-            SolveResult::NoInvalidElement => unreachable!(),
-            SolveResult::InvalidElement(element) => Ok(element)
+        first_invalid(_s, DEFAULT_PREAMBLE)
+    }
+}
+
+impl PartTwo for Day9 {
+    type OutputPartTwo = u64;
+
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+        encryption_weakness(s, DEFAULT_PREAMBLE)
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Ex9Error {
+    #[error("line {line}: invalid number `{text}`")]
+    InvalidNumber { line: usize, text: String },
+
+    #[error("every number is a sum of two of the preceding ones")]
+    NoInvalidElement,
+
+    #[error("no contiguous range of at least two numbers sums to {0}")]
+    NoWeakness(u64),
+}
+
+impl Locate for Ex9Error {
+    fn line(&self) -> Option<usize> {
+        match self {
+            Ex9Error::InvalidNumber { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    fn fragment(&self) -> Option<String> {
+        match self {
+            Ex9Error::InvalidNumber { text, .. } => Some(text.to_owned()),
+            _ => None,
         }
     }
 }
 
-fn is_sum_of_two(mut preamble: Vec<u16>, element: &u16) -> bool {
+fn is_sum_of_two(mut preamble: Vec<u64>, element: &u64) -> bool {
     preamble.sort_unstable();
 
+    if preamble.is_empty() {
+        return false;
+    }
+
     let mut right_border: usize = preamble.len() - 1;
     let mut index: usize = 0;
 
     while index != right_border {
-        let value1: u16 = preamble[index];
-        let value2: u16 = preamble[right_border];
-        let checked_sum: u16 = value1 + value2;
+        let value1: u64 = preamble[index];
+        let value2: u64 = preamble[right_border];
+        let checked_sum: u128 = u128::from(value1) + u128::from(value2);
 
-        if checked_sum <= *element {
-            if checked_sum == *element {
+        if checked_sum <= u128::from(*element) {
+            if checked_sum == u128::from(*element) {
                 return true;
             }
             index += 1;
@@ -40,17 +85,27 @@ fn is_sum_of_two(mut preamble: Vec<u16>, element: &u16) -> bool {
     false
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub enum SolveResult {
     NoInvalidElement,
-    InvalidElement(u16),
+    InvalidElement(u64),
 }
 
-fn solve(pattern: &str, preamble_size: usize) -> SolveResult {
-    let input: Vec<u16> = pattern
+pub fn parse_numbers(pattern: &str) -> Result<Vec<u64>, Ex9Error> {
+    pattern
         .lines()
-        .map(|f| f.parse::<u16>().unwrap())
-        .collect();
+        .enumerate()
+        .map(|(line_num, line)| {
+            line.parse::<u64>().map_err(|_| Ex9Error::InvalidNumber {
+                line: line_num + 1,
+                text: line.to_owned(),
+            })
+        })
+        .collect()
+}
+
+pub fn solve(pattern: &str, preamble_size: usize) -> Result<SolveResult, Ex9Error> {
+    let input = parse_numbers(pattern)?;
     let res = input
         .iter()
         .enumerate()
@@ -58,26 +113,96 @@ fn solve(pattern: &str, preamble_size: usize) -> SolveResult {
         .find(|elem| !is_sum_of_two((&input[elem.0 - preamble_size..elem.0]).to_vec(), elem.1));
 
     match res {
-        None => SolveResult::NoInvalidElement,
-        Some(elem) => SolveResult::InvalidElement(*elem.1),
+        None => Ok(SolveResult::NoInvalidElement),
+        Some(elem) => Ok(SolveResult::InvalidElement(*elem.1)),
     }
 }
 
+/// First number which is not a sum of two of the `preamble` numbers before it.
+pub fn first_invalid(s: &str, preamble: usize) -> anyhow::Result<u64> {
+    match solve(s, preamble).diagnose(s)? {
+        SolveResult::NoInvalidElement => Err(Ex9Error::NoInvalidElement.into()),
+        SolveResult::InvalidElement(element) => Ok(element),
+    }
+}
+
+/// Weakness of the first invalid number, see `first_invalid`.
+pub fn encryption_weakness(s: &str, preamble: usize) -> anyhow::Result<u64> {
+    let invalid = first_invalid(s, preamble)?;
+    let numbers = parse_numbers(s).diagnose(s)?;
+
+    Ok(weakness(&numbers, invalid)?)
+}
+
+/// Sum of the smallest and the largest number of the first contiguous range of at least two
+/// numbers adding up to `target`, found with a sliding window in a single pass.
+pub fn weakness(numbers: &[u64], target: u64) -> Result<u64, Ex9Error> {
+    let mut start: usize = 0;
+    let mut sum: u128 = 0;
+
+    for (end, number) in numbers.iter().enumerate() {
+        sum += u128::from(*number);
+
+        while sum > u128::from(target) && start < end {
+            sum -= u128::from(numbers[start]);
+            start += 1;
+        }
+
+        if sum == u128::from(target) && start < end {
+            let range = &numbers[start..=end];
+            return Ok(range.iter().min().unwrap() + range.iter().max().unwrap());
+        }
+    }
+
+    Err(Ex9Error::NoWeakness(target))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::read_to_string;
     use test_case::test_case;
 
-    // #[test_case("data_files/ex9.txt" => SolveResult::InvalidElement(127))]
-    // #[test_case("data_files/ex9_no_invalid.txt" => SolveResult::NoInvalidElement)]
-    // fn test_solve(s: &str) -> SolveResult {
-    //     let data = get_data(s).unwrap();
-    //     solve(data.as_str(), 5)
-    // }
+    #[test_case("data_files/ex9.txt" => SolveResult::InvalidElement(127))]
+    #[test_case("data_files/ex9_no_invalid.txt" => SolveResult::NoInvalidElement)]
+    fn test_solve_file(s: &str) -> SolveResult {
+        let data = read_to_string(s).unwrap();
+        solve(data.as_str(), DEFAULT_PREAMBLE).unwrap()
+    }
 
     #[test_case(vec![1,2,3], &10 => false)]
     #[test_case(vec![1,2,3], &5 => true)]
-    fn test_is_sum_of_two(preamble: Vec<u16>, element: &u16) -> bool {
+    #[test_case(vec![u64::MAX, 1], &u64::MAX => false; "sum above u64")]
+    fn test_is_sum_of_two(preamble: Vec<u64>, element: &u64) -> bool {
         is_sum_of_two(preamble, element)
     }
+
+    #[test_case("1\n2\n3" => Ok(SolveResult::NoInvalidElement); "no invalid")]
+    #[test_case("1\n2\n4" => Ok(SolveResult::InvalidElement(4)); "invalid")]
+    #[test_case("1\n-2\n3" => Err(Ex9Error::InvalidNumber{line: 2, text: "-2".to_owned()}); "invalid number")]
+    fn test_solve(s: &str) -> Result<SolveResult, Ex9Error> {
+        solve(s, 2)
+    }
+
+    #[test_case(&[35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127], 127 => Ok(62); "example")]
+    #[test_case(&[1, 4, 2, 6], 8 => Ok(8); "range at the end")]
+    #[test_case(&[10, 3, 7], 10 => Ok(10); "single number is not a range")]
+    #[test_case(&[1, 2, 3], 7 => Err(Ex9Error::NoWeakness(7)); "no range")]
+    fn test_weakness(numbers: &[u64], target: u64) -> Result<u64, Ex9Error> {
+        weakness(numbers, target)
+    }
+
+    #[test]
+    fn test_solution_part_two() {
+        let input = read_to_string("data_files/ex9.txt").unwrap();
+        assert_eq!(Day9::solution_part_two(&input).unwrap(), 62);
+    }
+
+    #[test_case(3 => Ok(25); "shorter preamble")]
+    #[test_case(7 => Ok(127); "longer preamble")]
+    #[test_case(8 => Err(Ex9Error::NoInvalidElement); "every number valid")]
+    fn test_first_invalid(preamble: usize) -> Result<u64, Ex9Error> {
+        let input = read_to_string("data_files/ex9.txt").unwrap();
+        first_invalid(&input, preamble).map_err(|err| err.downcast().unwrap())
+    }
 }
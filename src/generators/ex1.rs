@@ -0,0 +1,74 @@
+use super::Rng;
+
+const TARGET: u64 = 2020;
+
+/// Expense report with one planted pair and one planted triple summing to 2020,
+/// remaining entries are too big to be part of any other pair or triple.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let (pair, first, second) = loop {
+        let pair = rng.range(1..TARGET / 2);
+        let first = rng.range(1..TARGET / 4);
+        let second = rng.range(1..TARGET / 4);
+
+        if pair != first && pair != second && first != second && pair != first + second {
+            break (pair, first, second);
+        }
+    };
+
+    let mut entries = vec![
+        pair,
+        TARGET - pair,
+        first,
+        second,
+        TARGET - first - second,
+    ];
+
+    let forbidden = [
+        TARGET - pair,
+        TARGET - first,
+        TARGET - second,
+        TARGET - pair - first,
+        TARGET - pair - second,
+        TARGET - first - second,
+    ];
+
+    while entries.len() < size {
+        let entry = rng.range(TARGET / 2 + 1..TARGET);
+
+        if !forbidden.contains(&entry) {
+            entries.push(entry);
+        }
+    }
+
+    rng.shuffle(&mut entries);
+
+    entries
+        .iter()
+        .map(|entry| entry.to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex1::Day1;
+    use crate::part::PartTwo;
+    use aoc_utils::DaySolver;
+
+    #[test]
+    fn generated_ex1_has_single_pair() {
+        let input = generate(&mut Rng::with_seed(1), 200);
+        let entries: Vec<u64> = input.lines().map(|line| line.parse().unwrap()).collect();
+
+        let pairs = (0..entries.len())
+            .flat_map(|i| (i + 1..entries.len()).map(move |j| (i, j)))
+            .filter(|&(i, j)| entries[i] + entries[j] == TARGET)
+            .count();
+
+        assert_eq!(entries.len(), 200);
+        assert_eq!(pairs, 1);
+        assert!(Day1::solution(&input).is_ok());
+        assert!(Day1::solution_part_two(&input).is_ok());
+    }
+}
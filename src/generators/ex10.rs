@@ -0,0 +1,47 @@
+use super::Rng;
+
+/// Longest run of 1-jolt differences, same as in real puzzle inputs.
+const MAX_ONES_RUN: usize = 4;
+
+/// `size` adapters which chain with 1 and 3 jolts differences only.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut joltage = 0;
+    let mut ones_run = 0;
+
+    let mut adapters: Vec<u64> = (0..size)
+        .map(|_| {
+            if ones_run < MAX_ONES_RUN && rng.chance(70) {
+                ones_run += 1;
+                joltage += 1;
+            } else {
+                ones_run = 0;
+                joltage += 3;
+            }
+            joltage
+        })
+        .collect();
+
+    rng.shuffle(&mut adapters);
+
+    adapters
+        .iter()
+        .map(|adapter| adapter.to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex10::{connect_adapters, Day10};
+    use crate::part::PartTwo;
+
+    #[test]
+    fn generated_ex10_connects() {
+        let input = generate(&mut Rng::with_seed(10), 100);
+
+        assert_eq!(input.lines().count(), 100);
+        assert!(connect_adapters(&input).is_ok());
+        assert!(Day10::solution_part_two(&input).is_ok());
+    }
+}
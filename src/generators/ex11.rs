@@ -0,0 +1,175 @@
+use super::Rng;
+use crate::ex11::{Neighbourhood, SeatingRules, MAX_ROUNDS};
+
+pub const WIDTH: usize = 92;
+
+/// Percent of seats in random areas, lowered after each area which does not settle.
+const DENSITIES: [u64; 4] = [70, 60, 50, 40];
+
+/// Seating area as a flat vector, `true` stands for a seat and `false` for floor.
+struct Area {
+    seats: Vec<bool>,
+    rows:  usize,
+}
+
+impl Area {
+    fn random(rng: &mut Rng, rows: usize, density: u64) -> Self {
+        Area {
+            seats: (0..rows * WIDTH).map(|_| rng.chance(density)).collect(),
+            rows,
+        }
+    }
+
+    /// Seats watched from the seat at `index`, found without the solvers under test.
+    fn neighbours(&self, index: usize, neighbourhood: Neighbourhood) -> Vec<usize> {
+        let (row, col) = ((index / WIDTH) as i64, (index % WIDTH) as i64);
+        let mut found = Vec::new();
+
+        for (row_step, col_step) in (-1..=1).flat_map(|dr| (-1..=1).map(move |dc| (dr, dc))) {
+            if (row_step, col_step) == (0, 0) {
+                continue;
+            }
+
+            let (mut r, mut c) = (row + row_step, col + col_step);
+
+            while (0..self.rows as i64).contains(&r) && (0..WIDTH as i64).contains(&c) {
+                let other = r as usize * WIDTH + c as usize;
+
+                if self.seats[other] {
+                    found.push(other);
+                    break;
+                }
+                if neighbourhood == Neighbourhood::Adjacent {
+                    break;
+                }
+
+                r += row_step;
+                c += col_step;
+            }
+        }
+
+        found
+    }
+
+    /// Whether seating stops changing within `MAX_ROUNDS` rounds. A state repeating the one
+    /// from two rounds before flips forever, so such areas are rejected early.
+    fn settles(&self, rules: &SeatingRules) -> bool {
+        let neighbours: Vec<Vec<usize>> = (0..self.seats.len())
+            .map(|index| match self.seats[index] {
+                true => self.neighbours(index, rules.neighbourhood),
+                false => Vec::new(),
+            })
+            .collect();
+        let mut occupied = vec![false; self.seats.len()];
+        let mut previous = occupied.clone();
+
+        for _ in 0..MAX_ROUNDS {
+            let next: Vec<bool> = neighbours
+                .iter()
+                .enumerate()
+                .map(|(index, watched)| {
+                    let count = watched.iter().filter(|other| occupied[**other]).count();
+
+                    match occupied[index] {
+                        true => count < usize::from(rules.tolerance),
+                        false => self.seats[index] && count == 0,
+                    }
+                })
+                .collect();
+
+            if next == occupied {
+                return true;
+            }
+            if next == previous {
+                return false;
+            }
+
+            previous = std::mem::replace(&mut occupied, next);
+        }
+
+        false
+    }
+
+    /// Removes every seat which sees more than three others. Removing a seat never adds a
+    /// neighbour to the remaining ones, so all of them are taken in the first round and never
+    /// left under either rules.
+    fn thinned(mut self) -> Self {
+        for index in 0..self.seats.len() {
+            if self.seats[index] && self.neighbours(index, Neighbourhood::Visible).len() > 3 {
+                self.seats[index] = false;
+            }
+        }
+
+        self
+    }
+
+    fn text(&self) -> String {
+        self.seats
+            .chunks(WIDTH)
+            .map(|row| {
+                row.iter()
+                    .map(|seat| if *seat { 'L' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+/// Empty seating area of `size` rows, as wide as the real puzzle input. Dense random areas
+/// tend to flip between two states forever, so only areas which settle under both rules are
+/// returned, the fallback is a random area thinned until it settles by construction.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let rows = size.max(1);
+    let rules = [SeatingRules::PART_ONE, SeatingRules::PART_TWO];
+
+    for density in DENSITIES {
+        let area = Area::random(rng, rows, density);
+
+        if rules.iter().all(|rules| area.settles(rules)) {
+            return area.text();
+        }
+    }
+
+    Area::random(rng, rows, DENSITIES[0]).thinned().text()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex11::{count_occupied_seats, Day11VariantA};
+    use crate::part::PartTwo;
+    use aoc_utils::DaySolver;
+
+    #[test]
+    fn generated_ex11_shape() {
+        let input = generate(&mut Rng::with_seed(11), 40);
+
+        assert_eq!(input.lines().count(), 40);
+        assert!(input
+            .lines()
+            .all(|line| line.len() == WIDTH && line.chars().all(|c| c == 'L' || c == '.')));
+        assert!(input.lines().any(|line| line[1..].contains('L')));
+        assert!(Day11VariantA::solution(&input).is_ok());
+        assert!(Day11VariantA::solution_part_two(&input).is_ok());
+    }
+
+    #[test]
+    fn generated_ex11_dense_area_flips() {
+        let area = Area::random(&mut Rng::with_seed(11), 40, 90);
+
+        assert!(!area.settles(&SeatingRules::PART_ONE));
+        assert!(count_occupied_seats(&area.text(), &SeatingRules::PART_ONE).is_err());
+    }
+
+    #[test]
+    fn generated_ex11_thinned_area_settles() {
+        let area = Area::random(&mut Rng::with_seed(11), 40, 90).thinned();
+        let seats = area.seats.iter().filter(|seat| **seat).count();
+
+        for rules in [SeatingRules::PART_ONE, SeatingRules::PART_TWO] {
+            assert!(area.settles(&rules));
+            assert_eq!(count_occupied_seats(&area.text(), &rules).unwrap(), seats);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use super::Rng;
+
+/// `size` navigation instructions, turns are always multiples of 90 degrees.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let action = *rng.choose(&['N', 'S', 'E', 'W', 'L', 'R', 'F']);
+
+            let value = match action {
+                'L' | 'R' => 90 * rng.range(1..4),
+                _ => rng.range(1..100),
+            };
+
+            format!("{}{}", action, value)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex12::{Day12, Day12Strict};
+    use crate::part::PartTwo;
+    use aoc_utils::DaySolver;
+
+    #[test]
+    fn generated_ex12_solves() {
+        let input = generate(&mut Rng::with_seed(12), 100);
+
+        assert_eq!(input.lines().count(), 100);
+        assert!(Day12::solution(&input).is_ok());
+        assert!(Day12Strict::solution(&input).is_ok());
+        assert!(Day12Strict::solution_part_two(&input).is_ok());
+    }
+}
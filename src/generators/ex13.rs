@@ -0,0 +1,50 @@
+use super::Rng;
+
+/// Bus ids are distinct primes, so the part two timestamp always exists and product of any
+/// ten of them still fits into `u64`.
+const BUSES: [u64; 22] = [
+    7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+];
+
+const MAX_BUSES: usize = 10;
+
+/// Schedule of `size` slots, first one is always in service.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let slots_count = size.max(2);
+    let buses_count = (slots_count / 6 + 1).clamp(2, MAX_BUSES);
+
+    let mut buses = BUSES.to_vec();
+    rng.shuffle(&mut buses);
+
+    let mut positions: Vec<usize> = (1..slots_count).collect();
+    rng.shuffle(&mut positions);
+    positions.truncate(buses_count - 1);
+    positions.push(0);
+
+    let mut slots: Vec<String> = vec!["x".to_owned(); slots_count];
+
+    for (position, bus) in positions.iter().zip(buses) {
+        slots[*position] = bus.to_string();
+    }
+
+    format!("{}\n{}", rng.range(100_000..1_000_000), slots.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex13::Day13VariantA;
+    use crate::part::PartTwo;
+
+    #[test]
+    fn generated_ex13_shape() {
+        let input = generate(&mut Rng::with_seed(13), 60);
+        let (timestamp, schedule) = input.split_once('\n').unwrap();
+
+        assert!(timestamp.parse::<u64>().is_ok());
+        assert_eq!(schedule.split(',').count(), 60);
+        assert_eq!(schedule.split(',').filter(|bus| *bus != "x").count(), MAX_BUSES);
+        assert_ne!(schedule.split(',').next(), Some("x"));
+        assert!(Day13VariantA::solution_part_two(&input).is_ok());
+    }
+}
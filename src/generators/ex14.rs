@@ -0,0 +1,61 @@
+use super::Rng;
+
+const MASK_LEN: usize = 36;
+
+/// Real puzzle masks never float more than 9 bits, so part two writes fan out to at most
+/// 512 addresses.
+const MAX_FLOATING: usize = 9;
+
+fn mask(rng: &mut Rng) -> String {
+    let mut bits: Vec<char> = (0..MASK_LEN)
+        .map(|_| if rng.chance(50) { '1' } else { '0' })
+        .collect();
+
+    let floating = rng.index(MAX_FLOATING + 1);
+    let mut positions: Vec<usize> = (0..MASK_LEN).collect();
+    rng.shuffle(&mut positions);
+
+    for position in &positions[..floating] {
+        bits[*position] = 'X';
+    }
+
+    bits.into_iter().collect()
+}
+
+/// Docking program of about `size` lines, each mask is followed by a few memory writes.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut lines: Vec<String> = Vec::new();
+
+    while lines.len() < size.max(2) {
+        lines.push(format!("mask = {}", mask(rng)));
+
+        for _ in 0..rng.range(1..7) {
+            lines.push(format!(
+                "mem[{}] = {}",
+                rng.range(0..65_536),
+                rng.range(0..1_000_000_000)
+            ));
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex14::{Day14VariantA, Instruction};
+    use crate::part::PartTwo;
+
+    #[test]
+    fn generated_ex14_parses() {
+        let input = generate(&mut Rng::with_seed(14), 100);
+
+        assert!(input.lines().count() >= 100);
+        assert!(input.starts_with("mask = "));
+        assert!(input
+            .lines()
+            .all(|line| line.parse::<Instruction>().is_ok()));
+        assert!(Day14VariantA::solution_part_two(&input).is_ok());
+    }
+}
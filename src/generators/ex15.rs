@@ -0,0 +1,34 @@
+use super::Rng;
+
+const MAX_STARTING_NUMBERS: usize = 20;
+
+/// Up to 20 distinct starting numbers, turn count and not the input drives cost of this day.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut numbers: Vec<u64> = (0..30).collect();
+    rng.shuffle(&mut numbers);
+
+    numbers[..size.clamp(2, MAX_STARTING_NUMBERS)]
+        .iter()
+        .map(|number| number.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex15::Day15VersionA;
+    use crate::ex15c::Day15VersionC;
+    use aoc_utils::DaySolver;
+
+    #[test]
+    fn generated_ex15_solves() {
+        let input = generate(&mut Rng::with_seed(15), 7);
+
+        assert_eq!(input.split(',').count(), 7);
+        assert_eq!(
+            Day15VersionA::solution(&input).unwrap(),
+            Day15VersionC::solution(&input).unwrap()
+        );
+    }
+}
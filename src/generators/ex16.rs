@@ -0,0 +1,106 @@
+use super::Rng;
+
+const FIELDS: [&str; 20] = [
+    "departure location",
+    "departure station",
+    "departure platform",
+    "departure track",
+    "departure date",
+    "departure time",
+    "arrival location",
+    "arrival station",
+    "arrival platform",
+    "arrival track",
+    "class",
+    "duration",
+    "price",
+    "route",
+    "row",
+    "seat",
+    "train",
+    "type",
+    "wagon",
+    "zone",
+];
+
+/// Values below and above of these never match any field.
+const LOWEST_VALID: u64 = 25;
+const HIGHEST_VALID: u64 = 974;
+
+type FieldRanges = [(u64, u64); 2];
+
+fn field_ranges(rng: &mut Rng) -> FieldRanges {
+    let low_start = rng.range(LOWEST_VALID..60);
+    let low_end = rng.range(low_start + 200..low_start + 500);
+    let high_start = low_end + rng.range(5..40);
+    let high_end = rng.range(high_start + 200..HIGHEST_VALID + 1);
+
+    [(low_start, low_end), (high_start, high_end)]
+}
+
+fn ticket(rng: &mut Rng, columns: &[FieldRanges], with_error: bool) -> String {
+    let error_column = rng.index(columns.len());
+
+    columns
+        .iter()
+        .enumerate()
+        .map(|(column, ranges)| {
+            if with_error && column == error_column {
+                if rng.chance(50) {
+                    rng.range(0..LOWEST_VALID)
+                } else {
+                    rng.range(HIGHEST_VALID + 1..1000)
+                }
+            } else {
+                let (start, end) = *rng.choose(ranges);
+                rng.range(start..end + 1)
+            }
+        })
+        .map(|value| value.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Notes with 20 fields and `size` nearby tickets, a quarter of them has one invalid value.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let ranges: Vec<FieldRanges> = FIELDS.iter().map(|_| field_ranges(rng)).collect();
+
+    let rules = FIELDS
+        .iter()
+        .zip(&ranges)
+        .map(|(field, [(a, b), (c, d)])| format!("{}: {}-{} or {}-{}", field, a, b, c, d))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let mut columns = ranges;
+    rng.shuffle(&mut columns);
+
+    let your_ticket = ticket(rng, &columns, false);
+    let nearby_tickets = (0..size)
+        .map(|_| {
+            let with_error = rng.chance(25);
+            ticket(rng, &columns, with_error)
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    format!(
+        "{}\n\nyour ticket:\n{}\n\nnearby tickets:\n{}",
+        rules, your_ticket, nearby_tickets
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex16::Day16;
+    use aoc_utils::DaySolver;
+
+    #[test]
+    fn generated_ex16_solves() {
+        let input = generate(&mut Rng::with_seed(16), 100);
+
+        assert_eq!(input.lines().count(), FIELDS.len() + 1 + 2 + 1 + 1 + 100);
+        assert!(Day16::solution(&input).unwrap() > 0);
+    }
+}
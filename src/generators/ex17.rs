@@ -0,0 +1,31 @@
+use super::Rng;
+
+/// Initial `size` x `size` slice of the pocket dimension.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(1);
+
+    (0..side)
+        .map(|_| {
+            (0..side)
+                .map(|_| if rng.chance(40) { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex17::Day17;
+    use crate::ex17other::Day17other;
+    use aoc_utils::DaySolver;
+
+    #[test]
+    fn generated_ex17_solves() {
+        let input = generate(&mut Rng::with_seed(17), 5);
+
+        assert!(Day17::solution(&input).is_ok());
+        assert!(Day17other::solution(&input).is_ok());
+    }
+}
@@ -0,0 +1,45 @@
+use super::Rng;
+
+const MAX_DEPTH: usize = 2;
+
+fn expression(rng: &mut Rng, depth: usize) -> String {
+    let operands = rng.range(2..5);
+    let mut result = String::new();
+
+    for index in 0..operands {
+        if index > 0 {
+            result.push_str(if rng.chance(50) { " + " } else { " * " });
+        }
+
+        if depth < MAX_DEPTH && rng.chance(25) {
+            result.push_str(&format!("({})", expression(rng, depth + 1)));
+        } else {
+            result.push_str(&rng.range(1..10).to_string());
+        }
+    }
+
+    result
+}
+
+/// `size` expressions of single digit numbers with at most two levels of parentheses.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| expression(rng, 0))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex18::Day18;
+    use aoc_utils::DaySolver;
+
+    #[test]
+    fn generated_ex18_solves() {
+        let input = generate(&mut Rng::with_seed(18), 20);
+
+        assert_eq!(input.lines().count(), 20);
+        assert!(Day18::solution(&input).is_ok());
+    }
+}
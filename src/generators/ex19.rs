@@ -0,0 +1,135 @@
+use super::Rng;
+
+/// Every rule above the letters joins two rules of the level below, so all messages
+/// matching the top rule are `TOP_LEN * 2^LEVELS` letters long.
+const LEVELS: usize = 3;
+const RULES_PER_LEVEL: usize = 6;
+const TOP_LEN: usize = 3;
+
+enum Rule {
+    Letter(char),
+    Sequence(Vec<usize>),
+    Alternative(Vec<usize>, Vec<usize>),
+}
+
+fn pair(rng: &mut Rng, level: &[usize]) -> Vec<usize> {
+    vec![*rng.choose(level), *rng.choose(level)]
+}
+
+/// Rules indexed by their position, rule 0 is the top one.
+fn grammar(rng: &mut Rng) -> Vec<Rule> {
+    let mut rules = vec![Rule::Sequence(Vec::new()), Rule::Letter('a'), Rule::Letter('b')];
+    let mut previous_level: Vec<usize> = vec![1, 2];
+
+    for _ in 0..LEVELS {
+        let mut level = Vec::new();
+
+        for _ in 0..RULES_PER_LEVEL {
+            let rule = if rng.chance(30) {
+                Rule::Sequence(pair(rng, &previous_level))
+            } else {
+                Rule::Alternative(pair(rng, &previous_level), pair(rng, &previous_level))
+            };
+
+            rules.push(rule);
+            level.push(rules.len() - 1);
+        }
+
+        previous_level = level;
+    }
+
+    rules[0] = Rule::Sequence((0..TOP_LEN).map(|_| *rng.choose(&previous_level)).collect());
+    rules
+}
+
+fn derive(rng: &mut Rng, rules: &[Rule], rule: usize, message: &mut String) {
+    let sequence = match &rules[rule] {
+        Rule::Letter(letter) => {
+            message.push(*letter);
+            return;
+        },
+        Rule::Sequence(sequence) => sequence,
+        Rule::Alternative(left, right) => {
+            if rng.chance(50) {
+                left
+            } else {
+                right
+            }
+        },
+    };
+
+    for sub_rule in sequence {
+        derive(rng, rules, *sub_rule, message);
+    }
+}
+
+fn format_rule(rule: &Rule, ids: &[usize]) -> String {
+    let sequence = |sequence: &[usize]| {
+        sequence
+            .iter()
+            .map(|rule| ids[*rule].to_string())
+            .collect::<Vec<String>>()
+            .join(" ")
+    };
+
+    match rule {
+        Rule::Letter(letter) => format!("\"{}\"", letter),
+        Rule::Sequence(body) => sequence(body),
+        Rule::Alternative(left, right) => format!("{} | {}", sequence(left), sequence(right)),
+    }
+}
+
+/// Grammar of a fixed shape with shuffled rule numbers and `size` messages, about half of
+/// them derived from rule 0 and the rest with one letter flipped.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let rules = grammar(rng);
+
+    let mut ids: Vec<usize> = (1..rules.len()).collect();
+    rng.shuffle(&mut ids);
+    ids.insert(0, 0);
+
+    let mut rules_lines: Vec<String> = rules
+        .iter()
+        .enumerate()
+        .map(|(index, rule)| format!("{}: {}", ids[index], format_rule(rule, &ids)))
+        .collect();
+    rng.shuffle(&mut rules_lines);
+
+    let messages: Vec<String> = (0..size)
+        .map(|_| {
+            let mut message = String::new();
+            derive(rng, &rules, 0, &mut message);
+
+            if rng.chance(50) {
+                let flipped = rng.index(message.len());
+                let letter = if &message[flipped..=flipped] == "a" { "b" } else { "a" };
+                message.replace_range(flipped..=flipped, letter);
+            }
+
+            message
+        })
+        .collect();
+
+    format!("{}\n\n{}", rules_lines.join("\n"), messages.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex19::Day19;
+    use crate::ex19petgraph::Day19petgraph;
+    use aoc_utils::DaySolver;
+
+    #[test]
+    fn generated_ex19_solves() {
+        let input = generate(&mut Rng::with_seed(19), 20);
+        let (rules, messages) = input.split_once("\n\n").unwrap();
+
+        assert_eq!(rules.lines().count(), 3 + LEVELS * RULES_PER_LEVEL);
+        assert!(messages
+            .lines()
+            .all(|message| message.len() == TOP_LEN << LEVELS));
+        assert!(Day19::solution(&input).unwrap() > 0);
+        assert!(Day19petgraph::solution(&input).unwrap() > 0);
+    }
+}
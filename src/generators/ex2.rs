@@ -0,0 +1,40 @@
+use super::Rng;
+
+/// Password lines, roughly a third of them valid under each policy.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let min = rng.range(1..10);
+            let max = rng.range(min + 1..min + 10);
+            let letter = rng.letter();
+            let len = rng.range(max..max + 8);
+
+            let password: String = (0..len)
+                .map(|_| {
+                    if rng.chance(25) {
+                        letter
+                    } else {
+                        rng.letter()
+                    }
+                })
+                .collect();
+
+            format!("{}-{} {}: {}", min, max, letter, password)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex2::Password;
+
+    #[test]
+    fn generated_ex2_parses() {
+        let input = generate(&mut Rng::with_seed(2), 100);
+
+        assert_eq!(input.lines().count(), 100);
+        assert!(input.lines().all(|line| line.parse::<Password>().is_ok()));
+    }
+}
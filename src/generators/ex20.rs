@@ -0,0 +1,98 @@
+use super::Rng;
+
+const TILE: usize = 10;
+
+/// Both solvers arrange exactly 3 x 3 tiles.
+const SIDE: usize = 3;
+
+type Pixels = Vec<Vec<char>>;
+
+fn rotate(tile: &Pixels) -> Pixels {
+    (0..TILE)
+        .map(|row| (0..TILE).map(|col| tile[TILE - 1 - col][row]).collect())
+        .collect()
+}
+
+fn format_tile(id: u64, tile: &Pixels) -> String {
+    let rows: Vec<String> = tile.iter().map(|row| row.iter().collect()).collect();
+    format!("Tile {}:\n{}", id, rows.join("\n"))
+}
+
+/// Tiles in row order, cut from one random picture so the neighbours share their borders.
+fn image(rng: &mut Rng, side: usize) -> Vec<(u64, Pixels)> {
+    let picture_side = (TILE - 1) * side + 1;
+
+    let picture: Pixels = (0..picture_side)
+        .map(|_| {
+            (0..picture_side)
+                .map(|_| if rng.chance(50) { '#' } else { '.' })
+                .collect()
+        })
+        .collect();
+
+    let mut ids: Vec<u64> = Vec::new();
+    while ids.len() < side * side {
+        let id = rng.range(1000..10000);
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+
+    ids.into_iter()
+        .enumerate()
+        .map(|(index, id)| {
+            let (top, left) = ((index / side) * (TILE - 1), (index % side) * (TILE - 1));
+
+            let tile = picture[top..top + TILE]
+                .iter()
+                .map(|row| row[left..left + TILE].to_vec())
+                .collect();
+
+            (id, tile)
+        })
+        .collect()
+}
+
+/// Image of 3 x 3 tiles, each one rotated, maybe flipped, and listed in random order. The
+/// solvers accept no other layout, so `size` does not change the input.
+pub fn generate(rng: &mut Rng, _size: usize) -> String {
+    let mut tiles: Vec<String> = Vec::new();
+
+    for (id, mut tile) in image(rng, SIDE) {
+        for _ in 0..rng.range(0..4) {
+            tile = rotate(&tile);
+        }
+        if rng.chance(50) {
+            tile.reverse();
+        }
+
+        tiles.push(format_tile(id, &tile));
+    }
+
+    rng.shuffle(&mut tiles);
+    tiles.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex20::Day20;
+    use crate::ex20b::Day20b;
+    use aoc_utils::DaySolver;
+
+    #[test]
+    fn generated_ex20_solves() {
+        let corners: u128 = image(&mut Rng::with_seed(20), SIDE)
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| [0, 2, 6, 8].contains(index))
+            .map(|(_, (id, _))| u128::from(*id))
+            .product();
+
+        let input = generate(&mut Rng::with_seed(20), 100);
+
+        assert_eq!(input.matches("Tile ").count(), 9);
+        assert_eq!(Day20::solution(&input).unwrap(), corners);
+        assert_eq!(Day20b::solution(&input).unwrap(), corners);
+    }
+}
@@ -0,0 +1,62 @@
+use super::Rng;
+
+const ALLERGENS: [&str; 9] = [
+    "dairy", "eggs", "fish", "nuts", "peanuts", "sesame", "shellfish", "soy", "wheat",
+];
+
+/// `size` foods, every allergen is hidden in exactly one ingredient and each food lists
+/// at least one of the allergens it contains.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut ingredients: Vec<String> = Vec::new();
+    while ingredients.len() < 40 {
+        let ingredient = rng.word(3..8);
+        if !ingredients.contains(&ingredient) {
+            ingredients.push(ingredient);
+        }
+    }
+
+    let allergens_count = 1 + rng.index(ALLERGENS.len());
+    // allergen `n` is in ingredient `n`
+    let allergens = &ALLERGENS[..allergens_count];
+
+    (0..size)
+        .map(|_| {
+            let mut listed: Vec<usize> =
+                (0..allergens_count).filter(|_| rng.chance(30)).collect();
+            if listed.is_empty() {
+                listed.push(rng.index(allergens_count));
+            }
+
+            let mut food: Vec<&str> = listed
+                .iter()
+                .map(|allergen| ingredients[*allergen].as_str())
+                .collect();
+            for ingredient in &ingredients {
+                if rng.chance(25) && !food.contains(&ingredient.as_str()) {
+                    food.push(ingredient);
+                }
+            }
+            rng.shuffle(&mut food);
+
+            let listed: Vec<&str> = listed.iter().map(|allergen| allergens[*allergen]).collect();
+
+            format!("{} (contains {})", food.join(" "), listed.join(", "))
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex21::Day21;
+    use aoc_utils::DaySolver;
+
+    #[test]
+    fn generated_ex21_solves() {
+        let input = generate(&mut Rng::with_seed(21), 30);
+
+        assert_eq!(input.lines().count(), 30);
+        assert!(Day21::solution(&input).is_ok());
+    }
+}
@@ -0,0 +1,35 @@
+use super::Rng;
+
+/// Cards `1..=2n` dealt between two players, `n` is `size` clamped to 1..=127 so every card
+/// fits in a byte.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let deck_size = size.clamp(1, 127);
+
+    let mut cards: Vec<String> = (1..=2 * deck_size).map(|card| card.to_string()).collect();
+    rng.shuffle(&mut cards);
+
+    let (player1, player2) = cards.split_at(deck_size);
+
+    format!(
+        "Player 1:\n{}\n\nPlayer 2:\n{}",
+        player1.join("\n"),
+        player2.join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex22::Day22;
+    use aoc_utils::DaySolver;
+
+    #[test]
+    fn generated_ex22_solves() {
+        let input = generate(&mut Rng::with_seed(22), 5);
+
+        let decks: Vec<usize> = input.split("\n\n").map(|deck| deck.lines().count() - 1).collect();
+
+        assert_eq!(decks, [5, 5]);
+        assert!(Day22::solution(&input).unwrap() > 0);
+    }
+}
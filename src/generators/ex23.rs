@@ -0,0 +1,27 @@
+use super::Rng;
+
+/// Cups labelled 1..=9 in random order, the puzzle input has fixed length so `size` is ignored.
+pub fn generate(rng: &mut Rng, _size: usize) -> String {
+    let mut cups: Vec<char> = ('1'..='9').collect();
+    rng.shuffle(&mut cups);
+
+    cups.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex23::Day23;
+    use crate::ex23b::Day23b;
+    use aoc_utils::DaySolver;
+
+    #[test]
+    fn generated_ex23_solves() {
+        let input = generate(&mut Rng::with_seed(23), 0);
+
+        assert_eq!(
+            Day23::solution(&input).unwrap(),
+            Day23b::solution(&input).unwrap()
+        );
+    }
+}
@@ -0,0 +1,33 @@
+use super::Rng;
+
+const DIRECTIONS: [&str; 6] = ["e", "se", "sw", "w", "nw", "ne"];
+
+/// `size` paths of 5 to 20 steps each.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            (0..rng.range(5..21))
+                .map(|_| *rng.choose(&DIRECTIONS))
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex24::Day24;
+    use crate::ex24pdo::Day24b;
+    use aoc_utils::DaySolver;
+
+    #[test]
+    fn generated_ex24_solves() {
+        let input = generate(&mut Rng::with_seed(24), 50);
+
+        assert_eq!(
+            Day24::solution(&input).unwrap(),
+            Day24b::solution(&input).unwrap()
+        );
+    }
+}
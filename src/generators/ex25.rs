@@ -0,0 +1,37 @@
+use super::Rng;
+
+const SUBJECT: u64 = 7;
+const MODULO: u64 = 20201227;
+
+fn public_key(loop_size: u64) -> u64 {
+    (0..loop_size).fold(1, |value, _| value * SUBJECT % MODULO)
+}
+
+/// Public keys of the card and the door, both loop sizes are at most `size`.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let max_loop_size = size.max(1) as u64;
+
+    let card = public_key(rng.range(1..max_loop_size + 1));
+    let door = public_key(rng.range(1..max_loop_size + 1));
+
+    format!("{}\n{}", card, door)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex25::Day25;
+    use crate::ex25b::Day25b;
+    use aoc_utils::DaySolver;
+
+    #[test]
+    fn generated_ex25_solves() {
+        // base variant computes powers of 7 directly, so loop sizes have to stay small
+        let input = generate(&mut Rng::with_seed(25), 20);
+
+        assert_eq!(
+            Day25::solution(&input).unwrap().to_string(),
+            Day25b::solution(&input).unwrap().to_string()
+        );
+    }
+}
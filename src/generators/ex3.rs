@@ -0,0 +1,30 @@
+use super::Rng;
+
+pub const WIDTH: usize = 31;
+
+/// Map of `size` rows, as wide as the real puzzle input.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            (0..WIDTH)
+                .map(|_| if rng.chance(20) { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_ex3_shape() {
+        let input = generate(&mut Rng::with_seed(3), 50);
+
+        assert_eq!(input.lines().count(), 50);
+        assert!(input
+            .lines()
+            .all(|line| line.len() == WIDTH && line.chars().all(|c| c == '.' || c == '#')));
+    }
+}
@@ -0,0 +1,89 @@
+use super::Rng;
+
+const EYE_COLORS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+fn year(rng: &mut Rng, valid: std::ops::Range<u64>, broken: bool) -> String {
+    if broken {
+        rng.range(1900..2040).to_string()
+    } else {
+        rng.range(valid).to_string()
+    }
+}
+
+fn field_value(rng: &mut Rng, key: &str, broken: bool) -> String {
+    match key {
+        "byr" => year(rng, 1920..2003, broken),
+        "iyr" => year(rng, 2010..2021, broken),
+        "eyr" => year(rng, 2020..2031, broken),
+        "hgt" if broken => rng.range(50..200).to_string(),
+        "hgt" if rng.chance(50) => format!("{}cm", rng.range(150..194)),
+        "hgt" => format!("{}in", rng.range(59..77)),
+        "hcl" => {
+            let color: String = (0..6)
+                .map(|_| *rng.choose(b"0123456789abcdef") as char)
+                .collect();
+
+            if broken {
+                color
+            } else {
+                format!("#{}", color)
+            }
+        },
+        "ecl" if broken => rng.word(3..4),
+        "ecl" => rng.choose(&EYE_COLORS).to_string(),
+        "pid" => {
+            let len = if broken { rng.range(7..12) } else { 9 };
+            (0..len).map(|_| (b'0' + rng.range(0..10) as u8) as char).collect()
+        },
+        _ => rng.range(100..350).to_string(),
+    }
+}
+
+fn passport(rng: &mut Rng) -> String {
+    let mut fields: Vec<String> = Vec::new();
+
+    for key in ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"] {
+        let is_missing = rng.chance(5) || (key == "cid" && rng.chance(50));
+
+        if !is_missing {
+            let broken = rng.chance(5);
+            fields.push(format!("{}:{}", key, field_value(rng, key, broken)));
+        }
+    }
+
+    rng.shuffle(&mut fields);
+
+    let mut passport = String::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        if index > 0 {
+            passport.push(if rng.chance(30) { '\n' } else { ' ' });
+        }
+        passport.push_str(field);
+    }
+
+    passport
+}
+
+/// `size` passports, some of them miss a field and some have a field with invalid value.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| passport(rng))
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex4::Day4;
+    use aoc_utils::DaySolver;
+
+    #[test]
+    fn generated_ex4_solves() {
+        let input = generate(&mut Rng::with_seed(4), 100);
+
+        assert_eq!(input.split("\n\n").count(), 100);
+        assert!(Day4::solution(&input).unwrap() > 0);
+    }
+}
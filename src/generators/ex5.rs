@@ -0,0 +1,58 @@
+use super::Rng;
+
+const SEATS: u64 = 128 * 8;
+
+fn encode(seat_id: u64) -> String {
+    (0..10)
+        .rev()
+        .map(|bit| {
+            let is_set = seat_id & (1 << bit) != 0;
+
+            match (bit >= 3, is_set) {
+                (true, true) => 'B',
+                (true, false) => 'F',
+                (false, true) => 'R',
+                (false, false) => 'L',
+            }
+        })
+        .collect()
+}
+
+/// Boarding passes of a full block of seats with a single free seat inside of it.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let count = size.clamp(3, 1000) as u64;
+    let first = rng.range(8..SEATS - count - 8);
+    let free = rng.range(first + 1..first + count);
+
+    let mut passes: Vec<String> = (first..=first + count)
+        .filter(|seat_id| *seat_id != free)
+        .map(encode)
+        .collect();
+
+    rng.shuffle(&mut passes);
+
+    passes.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex5::Day5;
+    use crate::part::PartTwo;
+    use test_case::test_case;
+
+    #[test_case(567 => "BFFFBBFRRR")]
+    #[test_case(820 => "BBFFBBFRLL")]
+    fn generated_ex5_encode(seat_id: u64) -> String {
+        encode(seat_id)
+    }
+
+    #[test]
+    fn generated_ex5_shape() {
+        let input = generate(&mut Rng::with_seed(5), 100);
+
+        assert_eq!(input.lines().count(), 100);
+        assert!(input.lines().all(|line| line.len() == 10));
+        assert!(Day5::solution_part_two(&input).is_ok());
+    }
+}
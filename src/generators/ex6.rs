@@ -0,0 +1,49 @@
+use super::Rng;
+
+/// `size` groups, answers of each person share a common part with the rest of the group.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let common = rng.word(0..5);
+            let people = rng.range(1..6);
+
+            (0..people)
+                .map(|_| {
+                    let own = rng.word(0..6);
+                    let mut answers: Vec<char> = common.chars().chain(own.chars()).collect();
+                    answers.sort_unstable();
+                    answers.dedup();
+                    rng.shuffle(&mut answers);
+
+                    if answers.is_empty() {
+                        answers.push(rng.letter());
+                    }
+
+                    answers.into_iter().collect::<String>()
+                })
+                .collect::<Vec<String>>()
+                .join("\n")
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex6::{self, Aggregation};
+
+    #[test]
+    fn generated_ex6_counts_agree() {
+        let input = generate(&mut Rng::with_seed(6), 100);
+
+        assert_eq!(input.split("\n\n").count(), 100);
+
+        for aggregation in [Aggregation::Anyone, Aggregation::Everyone] {
+            assert_eq!(
+                ex6::count_answers(&input, aggregation).unwrap(),
+                ex6::count_answers4(&input, aggregation) as usize
+            );
+        }
+    }
+}
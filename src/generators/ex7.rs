@@ -0,0 +1,98 @@
+use super::Rng;
+
+const ADJECTIVES: [&str; 18] = [
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "mirrored",
+    "muted", "pale", "plaid", "posh", "shiny", "striped", "vibrant", "wavy",
+];
+
+const COLORS: [&str; 32] = [
+    "aqua", "beige", "black", "blue", "bronze", "brown", "chartreuse", "coral", "crimson", "cyan",
+    "fuchsia", "gold", "gray", "green", "indigo", "lavender", "lime", "magenta", "maroon", "olive",
+    "orange", "plum", "purple", "red", "salmon", "silver", "tan", "teal", "tomato", "turquoise",
+    "violet", "white",
+];
+
+const LAYERS: usize = 6;
+pub const TARGET: &str = "shiny gold";
+
+fn colors(rng: &mut Rng, count: usize) -> Vec<String> {
+    let mut colors: Vec<String> = (0..=count)
+        .map(|index| {
+            let adjective = ADJECTIVES[index % ADJECTIVES.len()];
+            let color = COLORS[(index / ADJECTIVES.len()) % COLORS.len()];
+            let round = index / (ADJECTIVES.len() * COLORS.len());
+
+            if round == 0 {
+                format!("{} {}", adjective, color)
+            } else {
+                format!("{}{} {}", adjective, round, color)
+            }
+        })
+        .filter(|color| color != TARGET)
+        .take(count)
+        .collect();
+
+    rng.shuffle(&mut colors);
+    colors
+}
+
+/// Rules of `size` bags arranged in layers, bags contain only bags from the next layer so
+/// nested counts stay far from overflowing. Shiny gold bag lives in the second layer.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let count = size.max(LAYERS * 2);
+    let mut bags = colors(rng, count - 1);
+    bags.insert(count / LAYERS + rng.index(count / LAYERS), TARGET.to_owned());
+
+    let layer_of = |index: usize| index * LAYERS / count;
+    let layer_start = |layer: usize| (layer * count).div_ceil(LAYERS);
+
+    let mut rules: Vec<String> = bags
+        .iter()
+        .enumerate()
+        .map(|(index, bag)| {
+            let layer = layer_of(index);
+            let next_layer = layer_start(layer + 1)..layer_start(layer + 2).min(count);
+
+            let mut inner: Vec<usize> = next_layer.collect();
+            rng.shuffle(&mut inner);
+            inner.truncate(rng.index(5));
+
+            if inner.is_empty() {
+                format!("{} bags contain no other bags.", bag)
+            } else {
+                let contained = inner
+                    .iter()
+                    .map(|inner_bag| {
+                        let quantity = rng.range(1..6);
+                        let noun = if quantity == 1 { "bag" } else { "bags" };
+                        format!("{} {} {}", quantity, bags[*inner_bag], noun)
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ");
+
+                format!("{} bags contain {}.", bag, contained)
+            }
+        })
+        .collect();
+
+    rng.shuffle(&mut rules);
+    rules.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex7::Day7;
+    use crate::part::PartTwo;
+    use aoc_utils::DaySolver;
+
+    #[test]
+    fn generated_ex7_solves() {
+        let input = generate(&mut Rng::with_seed(7), 200);
+
+        assert_eq!(input.lines().count(), 200);
+        assert_eq!(input.matches("shiny gold bags contain").count(), 1);
+        assert!(Day7::solution(&input).is_ok());
+        assert!(Day7::solution_part_two(&input).is_ok());
+    }
+}
@@ -0,0 +1,76 @@
+use super::Rng;
+
+type Instruction = (&'static str, i64);
+
+fn filler(rng: &mut Rng) -> Instruction {
+    let operation = *rng.choose(&["acc", "jmp", "nop"]);
+    (operation, rng.signed(-20..21))
+}
+
+/// Boot code of `size` instructions which loops forever and terminates once a single
+/// executed `jmp` is changed into `nop`.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let len = size.max(2);
+    let mut program: Vec<Option<Instruction>> = vec![None; len];
+    let mut executed: Vec<usize> = Vec::new();
+    let mut pc = 0;
+
+    // terminating path, visited instructions are never jumps backwards
+    while pc < len {
+        let instruction = match rng.range(0..4) {
+            0 if pc > 0 => ("jmp", rng.range(2..6).min((len - pc) as u64) as i64),
+            1 => ("nop", rng.signed(-100..101)),
+            _ => ("acc", rng.signed(-50..51)),
+        };
+
+        program[pc] = Some(instruction);
+        executed.push(pc);
+
+        pc += match instruction {
+            ("jmp", offset) => offset as usize,
+            _ => 1,
+        };
+    }
+
+    // corrupted instruction sends execution back to already visited one
+    let candidates: Vec<usize> = executed
+        .iter()
+        .copied()
+        .filter(|index| !matches!(program[*index], Some(("jmp", _))))
+        .collect();
+    let corrupted = *rng.choose(&candidates);
+    let targets: Vec<usize> = executed
+        .iter()
+        .copied()
+        .filter(|index| *index <= corrupted)
+        .collect();
+    let target = *rng.choose(&targets);
+
+    program[corrupted] = Some(("jmp", target as i64 - corrupted as i64));
+
+    program
+        .into_iter()
+        .map(|instruction| {
+            let (operation, argument) = instruction.unwrap_or_else(|| filler(rng));
+            format!("{} {:+}", operation, argument)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex8::Day8;
+    use crate::part::PartTwo;
+    use aoc_utils::DaySolver;
+
+    #[test]
+    fn generated_ex8_loops() {
+        let input = generate(&mut Rng::with_seed(8), 300);
+
+        assert_eq!(input.lines().count(), 300);
+        assert!(Day8::solution(&input).is_ok());
+        assert!(Day8::solution_part_two(&input).is_ok());
+    }
+}
@@ -0,0 +1,131 @@
+use super::Rng;
+use crate::ex9::DEFAULT_PREAMBLE as PREAMBLE;
+
+/// Smallest of `PREAMBLE` numbers at least doubles with every `PREAMBLE` valid numbers, so
+/// numbers start over from small ones before reaching this bound.
+const MAX_VALUE: u64 = 1 << 48;
+
+/// Invalid number is placed among first numbers, whose growth stays far below `MAX_VALUE`.
+const INVALID_WITHIN: usize = 100;
+
+const ATTEMPTS: usize = 1000;
+
+fn is_sum_of_two(window: &[u64], value: u64) -> bool {
+    window.iter().enumerate().any(|(index, first)| {
+        window[index + 1..]
+            .iter()
+            .any(|second| first != second && first + second == value)
+    })
+}
+
+/// Sum of two different numbers picked among the smallest ones of the window, which keeps the
+/// numbers growth as slow as possible.
+fn valid_number(rng: &mut Rng, window: &[u64]) -> u64 {
+    let mut smallest = window.to_vec();
+    smallest.sort_unstable();
+    smallest.dedup();
+    smallest.truncate(4);
+
+    let first = rng.index(smallest.len());
+    let second = (first + 1 + rng.index(smallest.len() - 1)) % smallest.len();
+
+    smallest[first] + smallest[second]
+}
+
+/// Sum of a contiguous range of earlier numbers, which is not a sum of any pair in the window.
+fn invalid_number(rng: &mut Rng, numbers: &[u64]) -> u64 {
+    let window = &numbers[numbers.len() - PREAMBLE..];
+
+    for _ in 0..ATTEMPTS {
+        let start = rng.index(numbers.len() - 2);
+        let len = 2 + rng.index((numbers.len() - start - 1).min(16));
+        let sum = numbers[start..start + len].iter().sum();
+
+        if !is_sum_of_two(window, sum) && !window.contains(&sum) {
+            return sum;
+        }
+    }
+
+    window.iter().max().unwrap() * 2 + 1
+}
+
+fn small_numbers(rng: &mut Rng) -> Vec<u64> {
+    let mut numbers: Vec<u64> = (1..=50).collect();
+    rng.shuffle(&mut numbers);
+    numbers.truncate(PREAMBLE);
+
+    numbers
+}
+
+/// XMAS data of `size` numbers with the preamble `Day9` uses. The first invalid number lies in
+/// the second half of the first `INVALID_WITHIN` numbers and is the sum of a contiguous range of
+/// previous numbers. Later numbers start over from small ones whenever they would exceed
+/// `MAX_VALUE`, which keeps any `size` within `u64`.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let len = size.max(PREAMBLE + 1);
+    let planted_len = len.min(INVALID_WITHIN);
+    let half = (planted_len / 2).max(PREAMBLE);
+    let invalid_at = half + rng.index(planted_len - half);
+
+    let mut numbers = small_numbers(rng);
+
+    while numbers.len() < len {
+        if numbers.len() == invalid_at {
+            let invalid = invalid_number(rng, &numbers);
+            numbers.push(invalid);
+            continue;
+        }
+
+        match valid_number(rng, &numbers[numbers.len() - PREAMBLE..]) {
+            next if next <= MAX_VALUE => numbers.push(next),
+            _ => numbers.extend(small_numbers(rng)),
+        }
+    }
+
+    numbers.truncate(len);
+
+    numbers
+        .iter()
+        .map(|number| number.to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex9::Day9;
+    use crate::part::PartTwo;
+    use aoc_utils::DaySolver;
+    use test_case::test_case;
+
+    fn invalid_indices(numbers: &[u64]) -> Vec<usize> {
+        (PREAMBLE..numbers.len())
+            .filter(|index| !is_sum_of_two(&numbers[index - PREAMBLE..*index], numbers[*index]))
+            .collect()
+    }
+
+    #[test]
+    fn generated_ex9_single_invalid_number() {
+        let input = generate(&mut Rng::with_seed(9), INVALID_WITHIN);
+        let numbers: Vec<u64> = input.lines().map(|line| line.parse().unwrap()).collect();
+
+        assert_eq!(numbers.len(), INVALID_WITHIN);
+        assert_eq!(invalid_indices(&numbers).len(), 1);
+    }
+
+    #[test_case(100)]
+    #[test_case(1000)]
+    #[test_case(10_000)]
+    fn generated_ex9_has_weakness(size: usize) {
+        let input = generate(&mut Rng::with_seed(9), size);
+        let numbers: Vec<u64> = input.lines().map(|line| line.parse().unwrap()).collect();
+        let first_invalid = invalid_indices(&numbers)[0];
+
+        assert_eq!(numbers.len(), size);
+        assert!(numbers.iter().all(|number| *number <= MAX_VALUE));
+        assert!((INVALID_WITHIN / 2..INVALID_WITHIN).contains(&first_invalid));
+        assert_eq!(Day9::solution(&input).unwrap(), numbers[first_invalid]);
+        assert!(Day9::solution_part_two(&input).is_ok());
+    }
+}
@@ -0,0 +1,193 @@
+//! Seeded generators of valid puzzle inputs, one module per day.
+//!
+//! Every generator takes the random source and a size (lines, groups, tiles per side, ...
+//! whatever drives the cost of given day) and returns input in the same format as the
+//! files in `data_files/`.
+
+use std::ops::Range;
+
+use super::registry::DayNum;
+
+pub mod ex1;
+pub mod ex10;
+pub mod ex11;
+pub mod ex12;
+pub mod ex13;
+pub mod ex14;
+pub mod ex15;
+pub mod ex16;
+pub mod ex17;
+pub mod ex18;
+pub mod ex19;
+pub mod ex2;
+pub mod ex20;
+pub mod ex21;
+pub mod ex22;
+pub mod ex23;
+pub mod ex24;
+pub mod ex25;
+pub mod ex3;
+pub mod ex4;
+pub mod ex5;
+pub mod ex6;
+pub mod ex7;
+pub mod ex8;
+pub mod ex9;
+
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// Generator of day `n` is stored under index `n - 1`.
+pub const GENERATORS: [Generator; 25] = [
+    ex1::generate,
+    ex2::generate,
+    ex3::generate,
+    ex4::generate,
+    ex5::generate,
+    ex6::generate,
+    ex7::generate,
+    ex8::generate,
+    ex9::generate,
+    ex10::generate,
+    ex11::generate,
+    ex12::generate,
+    ex13::generate,
+    ex14::generate,
+    ex15::generate,
+    ex16::generate,
+    ex17::generate,
+    ex18::generate,
+    ex19::generate,
+    ex20::generate,
+    ex21::generate,
+    ex22::generate,
+    ex23::generate,
+    ex24::generate,
+    ex25::generate,
+];
+
+pub fn generate(day: DayNum, seed: u64, size: usize) -> Option<String> {
+    let generator = GENERATORS.get(usize::from(day).checked_sub(1)?)?;
+
+    Some(generator(&mut Rng::with_seed(seed), size))
+}
+
+// -----------------------------------------------------------------------------
+
+/// SplitMix64, small and fully reproducible from the seed.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn with_seed(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Value from non empty `range`.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+
+    /// Value from non empty `range`.
+    pub fn signed(&mut self, range: Range<i64>) -> i64 {
+        range.start + (self.next_u64() % range.start.abs_diff(range.end)) as i64
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0..len as u64) as usize
+    }
+
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.range(0..100) < percent
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            let other = self.index(index + 1);
+            items.swap(index, other);
+        }
+    }
+
+    pub fn letter(&mut self) -> char {
+        (b'a' + self.range(0..26) as u8) as char
+    }
+
+    pub fn word(&mut self, len: Range<u64>) -> String {
+        let len = self.range(len);
+        (0..len).map(|_| self.letter()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use test_case::test_case;
+
+    #[test]
+    fn generators_are_reproducible() {
+        for day in 1..=25 {
+            assert_eq!(generate(day, 7, 10), generate(day, 7, 10), "day {}", day);
+        }
+    }
+
+    #[test_case(0 => None)]
+    #[test_case(26 => None)]
+    #[test_case(23 => Some(9))]
+    fn generators_generate(day: DayNum) -> Option<usize> {
+        generate(day, 1, 10).map(|input| input.len())
+    }
+
+    #[test_case(1; "smallest")]
+    #[test_case(10; "small")]
+    #[test_case(100; "default")]
+    fn generated_inputs_solve(size: usize) {
+        let failures: Vec<String> = registry::SOLVERS
+            .iter()
+            .filter_map(|entry| {
+                let input = generate(entry.day, 7, size).unwrap();
+                let err = (entry.solve)(&input).err()?;
+
+                Some(format!("day {} part {} [{}]: {}", entry.day, entry.part, entry.variant, err))
+            })
+            .collect();
+
+        assert!(failures.is_empty(), "{:#?}", failures);
+    }
+
+    #[test]
+    fn rng_range_bounds() {
+        let mut rng = Rng::with_seed(3);
+
+        for _ in 0..1000 {
+            assert!((5..8).contains(&rng.range(5..8)));
+            assert!((-3..2).contains(&rng.signed(-3..2)));
+        }
+    }
+
+    #[test]
+    fn rng_shuffle_keeps_elements() {
+        let mut rng = Rng::with_seed(3);
+        let mut items: Vec<u32> = (0..50).collect();
+
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..50).collect::<Vec<u32>>());
+
+        items.sort_unstable();
+        assert_eq!(items, (0..50).collect::<Vec<u32>>());
+    }
+}
@@ -0,0 +1,110 @@
+use aoc_utils::DaySolver;
+use std::convert::Infallible;
+use std::fmt;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::str::FromStr;
+use thiserror::Error;
+
+use super::diagnostic;
+
+pub const STDIN_MARK: &str = "-";
+
+// -----------------------------------------------------------------------------
+
+/// Where the puzzle input comes from, on the command line `-` stands for stdin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    Text(String),
+}
+
+#[derive(Error, Debug)]
+pub enum InputError {
+    #[error("can not read `{}`: {source}", path.display())]
+    File { path: PathBuf, source: io::Error },
+
+    #[error("can not read stdin: {0}")]
+    Stdin(#[source] io::Error),
+}
+
+impl InputSource {
+    pub fn read(&self) -> Result<String, InputError> {
+        match self {
+            InputSource::File(path) => {
+                std::fs::read_to_string(path).map_err(|source| InputError::File {
+                    path: path.to_owned(),
+                    source,
+                })
+            },
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(InputError::Stdin)?;
+
+                Ok(input)
+            },
+            InputSource::Text(text) => Ok(text.to_owned()),
+        }
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            STDIN_MARK => Ok(InputSource::Stdin),
+            path => Ok(InputSource::File(PathBuf::from(path))),
+        }
+    }
+}
+
+impl From<&str> for InputSource {
+    fn from(path: &str) -> Self {
+        InputSource::File(PathBuf::from(path))
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::Text(_) => write!(f, "<text>"),
+        }
+    }
+}
+
+/// Runs any day on input from a file, stdin or memory.
+pub fn solve<S: DaySolver>(source: &InputSource) -> anyhow::Result<S::Output> {
+    S::solution(&source.read()?)
+        .map_err(|err| diagnostic::with_file(err, &source.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ex18::Day18;
+    use test_case::test_case;
+
+    #[test_case("-" => InputSource::Stdin; "stdin")]
+    #[test_case("data_files/ex18.txt" => InputSource::File(PathBuf::from("data_files/ex18.txt")); "file")]
+    fn input_source_from_str(s: &str) -> InputSource {
+        s.parse::<InputSource>().unwrap()
+    }
+
+    #[test_case(InputSource::from("data_files/ex18.txt") => 26335; "file")]
+    #[test_case(InputSource::Text("1 + 2 * 3".to_owned()) => 9; "text")]
+    fn input_solve(source: InputSource) -> u32 {
+        solve::<Day18>(&source).unwrap()
+    }
+
+    #[test]
+    fn input_missing_file() {
+        let err = InputSource::from("aaa").read().unwrap_err();
+        assert!(err.to_string().starts_with("can not read `aaa`"));
+    }
+}
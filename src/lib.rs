@@ -1,3 +1,6 @@
+pub mod agreement;
+pub mod answers;
+pub mod diagnostic;
 pub mod ex1;
 pub mod ex7;
 pub mod ex10;
@@ -32,3 +35,8 @@ pub mod ex5;
 pub mod ex6;
 pub mod ex8;
 pub mod ex9;
+pub mod generators;
+pub mod input;
+pub mod measurements;
+pub mod part;
+pub mod registry;
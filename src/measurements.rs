@@ -0,0 +1,330 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+use super::diagnostic;
+use super::part::Part;
+use super::registry::{DayNum, SolverEntry};
+
+pub const CSV_HEADER: &str = "day,part,variant,input,input_size,wall_time_ns,iterations,result";
+
+// -----------------------------------------------------------------------------
+
+/// One timed run of a solver, `wall_time` is the mean of all iterations.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Measurement {
+    pub day:        DayNum,
+    pub part:       Part,
+    pub variant:    String,
+    pub input:      String,
+    pub input_size: usize,
+    pub wall_time:  Duration,
+    pub iterations: u32,
+    pub result:     String,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum MeasurementError {
+    #[error("expected 8 fields, found {0}")]
+    WrongFieldsNumber(usize),
+
+    #[error("invalid {name} `{text}`")]
+    InvalidField { name: &'static str, text: String },
+
+    #[error("unclosed quote")]
+    UnclosedQuote,
+
+    #[error("line {line}: {source}")]
+    InLine {
+        line:   usize,
+        source: Box<MeasurementError>,
+    },
+}
+
+/// `input_name` only labels the measurement, `input` is what the solver gets.
+pub fn measure(
+    entry: &SolverEntry,
+    input_name: &str,
+    input: &str,
+    iterations: u32,
+) -> anyhow::Result<Measurement> {
+    measure_with(entry, entry.solve, input_name, input, iterations)
+}
+
+/// Same as `measure`, but `input` is solved by `solve` instead of the solver of `entry`.
+pub fn measure_with(
+    entry: &SolverEntry,
+    solve: impl Fn(&str) -> anyhow::Result<String>,
+    input_name: &str,
+    input: &str,
+    iterations: u32,
+) -> anyhow::Result<Measurement> {
+    let iterations = iterations.max(1);
+
+    let start = Instant::now();
+    let mut result = solve(input).map_err(|err| diagnostic::with_file(err, input_name))?;
+    for _ in 1..iterations {
+        result = solve(input)?;
+    }
+    let elapsed = start.elapsed();
+
+    Ok(Measurement {
+        day:        entry.day,
+        part:       entry.part,
+        variant:    entry.variant.to_owned(),
+        input:      input_name.to_owned(),
+        input_size: input.len(),
+        wall_time:  elapsed / iterations,
+        iterations,
+        result,
+    })
+}
+
+// -----------------------------------------------------------------------------
+
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Splits one csv line, fields with commas are quoted and quotes inside them doubled.
+fn split_fields(s: &str) -> Result<Vec<String>, MeasurementError> {
+    let mut fields = vec![String::new()];
+    let mut in_quotes = false;
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let field = fields.last_mut().unwrap();
+
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            },
+            ('"', _) => in_quotes = !in_quotes,
+            (',', false) => fields.push(String::new()),
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(MeasurementError::UnclosedQuote);
+    }
+
+    Ok(fields)
+}
+
+fn parse_field<T: FromStr>(name: &'static str, text: &str) -> Result<T, MeasurementError> {
+    text.parse::<T>()
+        .map_err(|_| MeasurementError::InvalidField {
+            name,
+            text: text.to_owned(),
+        })
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{},{},{},{},{},{}",
+            self.day,
+            self.part,
+            escape(&self.variant),
+            escape(&self.input),
+            self.input_size,
+            self.wall_time.as_nanos(),
+            self.iterations,
+            escape(&self.result)
+        )
+    }
+}
+
+impl FromStr for Measurement {
+    type Err = MeasurementError;
+
+    // 15,1,hash map,data_files/ex15.txt,6,1532,10,436
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields = split_fields(s)?;
+
+        let [day, part, variant, input, input_size, wall_time, iterations, result] = &fields[..]
+        else {
+            return Err(MeasurementError::WrongFieldsNumber(fields.len()));
+        };
+
+        Ok(Measurement {
+            day:        parse_field("day", day)?,
+            part:       parse_field("part", part)?,
+            variant:    variant.to_owned(),
+            input:      input.to_owned(),
+            input_size: parse_field("input size", input_size)?,
+            wall_time:  Duration::from_nanos(parse_field("wall time", wall_time)?),
+            iterations: parse_field("iterations", iterations)?,
+            result:     result.to_owned(),
+        })
+    }
+}
+
+/// Parses results file, skipping the header and empty lines.
+pub fn parse_results(s: &str) -> Result<Vec<Measurement>, MeasurementError> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && *line != CSV_HEADER)
+        .map(|(line_num, line)| {
+            line.parse::<Measurement>()
+                .map_err(|err| MeasurementError::InLine {
+                    line:   line_num + 1,
+                    source: Box::new(err),
+                })
+        })
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
+
+/// Same solver and input measured in two results files, `None` when missing in one of them.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Comparison {
+    pub day:            DayNum,
+    pub part:           Part,
+    pub variant:        String,
+    pub input:          String,
+    pub before:         Option<Duration>,
+    pub after:          Option<Duration>,
+    pub result_changed: bool,
+}
+
+impl Comparison {
+    /// Positive when the solver got slower.
+    pub fn change_percent(&self) -> Option<f64> {
+        let (before, after) = (self.before?, self.after?);
+
+        if before.is_zero() {
+            return None;
+        }
+
+        Some((after.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0)
+    }
+}
+
+fn same_run(first: &Measurement, second: &Measurement) -> bool {
+    first.day == second.day
+        && first.part == second.part
+        && first.variant == second.variant
+        && first.input == second.input
+}
+
+pub fn compare(before: &[Measurement], after: &[Measurement]) -> Vec<Comparison> {
+    let mut comparisons: Vec<Comparison> = before
+        .iter()
+        .map(|old| {
+            let new = after.iter().find(|new| same_run(old, new));
+
+            Comparison {
+                day:            old.day,
+                part:           old.part,
+                variant:        old.variant.clone(),
+                input:          old.input.clone(),
+                before:         Some(old.wall_time),
+                after:          new.map(|new| new.wall_time),
+                result_changed: new.is_some_and(|new| new.result != old.result),
+            }
+        })
+        .collect();
+
+    comparisons.extend(
+        after
+            .iter()
+            .filter(|new| !before.iter().any(|old| same_run(old, new)))
+            .map(|new| Comparison {
+                day:            new.day,
+                part:           new.part,
+                variant:        new.variant.clone(),
+                input:          new.input.clone(),
+                before:         None,
+                after:          Some(new.wall_time),
+                result_changed: false,
+            }),
+    );
+
+    comparisons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use test_case::test_case;
+
+    fn measurement(variant: &str, wall_time_ns: u64, result: &str) -> Measurement {
+        Measurement {
+            day:        15,
+            part:       Part::One,
+            variant:    variant.to_owned(),
+            input:      "data_files/ex15.txt".to_owned(),
+            input_size: 6,
+            wall_time:  Duration::from_nanos(wall_time_ns),
+            iterations: 10,
+            result:     result.to_owned(),
+        }
+    }
+
+    #[test_case(measurement("hash map", 1532, "436") => "15,1,hash map,data_files/ex15.txt,6,1532,10,436"; "plain")]
+    #[test_case(measurement("hash map", 1532, "a,\"b\"") => "15,1,hash map,data_files/ex15.txt,6,1532,10,\"a,\"\"b\"\"\""; "quoted")]
+    fn measurements_to_csv(measurement: Measurement) -> String {
+        measurement.to_string()
+    }
+
+    #[test_case(measurement("hash map", 1532, "436"); "plain")]
+    #[test_case(measurement("hash, map", 1532, "a,\"b\""); "quoted")]
+    fn measurements_csv_round_trip(measurement: Measurement) {
+        assert_eq!(measurement.to_string().parse::<Measurement>(), Ok(measurement));
+    }
+
+    #[test_case("15,1,hash map,data_files/ex15.txt,6,1532,10" => MeasurementError::WrongFieldsNumber(7); "missing field")]
+    #[test_case("15,1,hash map,data_files/ex15.txt,6,fast,10,436" => MeasurementError::InvalidField{name: "wall time", text: "fast".to_owned()}; "invalid time")]
+    #[test_case("15,1,\"hash map,data_files/ex15.txt,6,1532,10,436" => MeasurementError::UnclosedQuote; "unclosed quote")]
+    fn measurements_parse_error(s: &str) -> MeasurementError {
+        s.parse::<Measurement>().unwrap_err()
+    }
+
+    #[test]
+    fn measurements_parse_results() {
+        let s = format!("{}\n\n{}\n", CSV_HEADER, measurement("hash map", 1, "436"));
+        assert_eq!(parse_results(&s), Ok(vec![measurement("hash map", 1, "436")]));
+
+        let s = format!("{}\n15,1", CSV_HEADER);
+        assert!(matches!(
+            parse_results(&s),
+            Err(MeasurementError::InLine { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn measurements_measure() {
+        let entry = registry::find(Some(18), None, None).next().unwrap();
+        let input = std::fs::read_to_string(entry.default_file).unwrap();
+        let measurement = measure(entry, entry.default_file, &input, 3).unwrap();
+
+        assert_eq!(measurement.result, "26335");
+        assert_eq!(measurement.iterations, 3);
+        assert!(measurement.input_size > 0);
+    }
+
+    #[test]
+    fn measurements_compare() {
+        let before = vec![measurement("base", 100, "436"), measurement("hash map", 100, "436")];
+        let after = vec![measurement("hash map", 50, "437"), measurement("new", 10, "436")];
+
+        let comparisons = compare(&before, &after);
+
+        assert_eq!(comparisons.len(), 3);
+        assert_eq!(comparisons[0].after, None);
+        assert_eq!(comparisons[1].change_percent(), Some(-50.0));
+        assert!(comparisons[1].result_changed);
+        assert_eq!(comparisons[2].before, None);
+    }
+}
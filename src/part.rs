@@ -0,0 +1,61 @@
+use aoc_utils::DaySolver;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PartError {
+    #[error("unknown part `{0}` (expected 1 or 2)")]
+    UnknownPart(String),
+}
+
+impl FromStr for Part {
+    type Err = PartError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" | "one" => Ok(Part::One),
+            "2" | "two" => Ok(Part::Two),
+            _ => Err(PartError::UnknownPart(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// Second question of the day, `DaySolver::solution` answers the first one.
+pub trait PartTwo: DaySolver {
+    type OutputPartTwo;
+
+    /// Input the second question is checked on by default, the one of `DaySolver::INFO` unless
+    /// the example differs.
+    const DEFAULT_FILE_PART_TWO: &'static str = <Self as DaySolver>::INFO.file;
+
+    fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("1" => Ok(Part::One))]
+    #[test_case("two" => Ok(Part::Two))]
+    #[test_case("3" => Err(PartError::UnknownPart("3".to_owned())))]
+    fn part_from_str(s: &str) -> Result<Part, PartError> {
+        s.parse::<Part>()
+    }
+}
@@ -0,0 +1,263 @@
+use aoc_utils::DaySolver;
+use std::fmt::Display;
+
+use super::diagnostic;
+use super::input::InputSource;
+use super::part::{Part, PartTwo};
+use super::{
+    ex1, ex10, ex11, ex12, ex13, ex14, ex14b, ex15, ex15b, ex15c, ex16, ex17, ex17other, ex18, ex19,
+    ex19petgraph, ex2, ex20, ex20b, ex21, ex22, ex23, ex23b, ex24, ex24pdo, ex25, ex25b, ex3, ex4,
+    ex5, ex6, ex7, ex8, ex9,
+};
+
+pub type DayNum = u8;
+
+// -----------------------------------------------------------------------------
+
+/// Type erased `DaySolver`, labels mirror the `DayInfo` of the wrapped solver.
+pub struct SolverEntry {
+    pub day:          DayNum,
+    pub part:         Part,
+    pub variant:      &'static str,
+    pub default_file: &'static str,
+    pub solve:        fn(&str) -> anyhow::Result<String>,
+}
+
+impl SolverEntry {
+    pub fn solve_input(&self, source: &InputSource) -> anyhow::Result<String> {
+        (self.solve)(&source.read()?)
+            .map_err(|err| diagnostic::with_file(err, &source.to_string()))
+    }
+
+    pub fn solve_default_file(&self) -> anyhow::Result<String> {
+        self.solve_input(&InputSource::from(self.default_file))
+    }
+}
+
+fn solve_to_string<S>(s: &str) -> anyhow::Result<String>
+where
+    S: DaySolver,
+    S::Output: Display,
+{
+    Ok(S::solution(s)?.to_string())
+}
+
+fn solve_part_two_to_string<S>(s: &str) -> anyhow::Result<String>
+where
+    S: PartTwo,
+    S::OutputPartTwo: Display,
+{
+    Ok(S::solution_part_two(s)?.to_string())
+}
+
+/// Number of the day from the `day_N` name in `DayInfo`, names without a number give `0`.
+const fn day_number(name: &str) -> DayNum {
+    let bytes = name.as_bytes();
+    let mut day: DayNum = 0;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index].is_ascii_digit() {
+            day = day * 10 + (bytes[index] - b'0');
+        }
+        index += 1;
+    }
+
+    day
+}
+
+macro_rules! solver {
+    (One, $solver:ty) => {
+        SolverEntry {
+            day:          day_number(<$solver as DaySolver>::INFO.name),
+            part:         Part::One,
+            variant:      <$solver as DaySolver>::INFO.variant,
+            default_file: <$solver as DaySolver>::INFO.file,
+            solve:        solve_to_string::<$solver>,
+        }
+    };
+    (Two, $solver:ty) => {
+        SolverEntry {
+            day:          day_number(<$solver as DaySolver>::INFO.name),
+            part:         Part::Two,
+            variant:      <$solver as DaySolver>::INFO.variant,
+            default_file: <$solver as PartTwo>::DEFAULT_FILE_PART_TWO,
+            solve:        solve_part_two_to_string::<$solver>,
+        }
+    };
+}
+
+pub const SOLVERS: &[SolverEntry] = &[
+    solver!(One, ex1::Day1),
+    solver!(Two, ex1::Day1),
+    solver!(One, ex2::Day2),
+    solver!(Two, ex2::Day2),
+    solver!(One, ex3::Day3),
+    solver!(Two, ex3::Day3),
+    solver!(One, ex4::Day4),
+    solver!(Two, ex4::Day4),
+    solver!(One, ex5::Day5),
+    solver!(Two, ex5::Day5),
+    solver!(One, ex6::Day6),
+    solver!(Two, ex6::Day6),
+    solver!(One, ex7::Day7),
+    solver!(Two, ex7::Day7),
+    solver!(One, ex8::Day8),
+    solver!(Two, ex8::Day8),
+    solver!(One, ex9::Day9),
+    solver!(Two, ex9::Day9),
+    solver!(One, ex10::Day10),
+    solver!(Two, ex10::Day10),
+    solver!(One, ex11::Day11VariantA),
+    solver!(Two, ex11::Day11VariantA),
+    solver!(One, ex11::Day11VariantB),
+    solver!(Two, ex11::Day11VariantB),
+    solver!(One, ex12::Day12),
+    solver!(Two, ex12::Day12),
+    solver!(One, ex12::Day12Strict),
+    solver!(Two, ex12::Day12Strict),
+    solver!(One, ex13::Day13VariantA),
+    solver!(Two, ex13::Day13VariantA),
+    solver!(One, ex14::Day14VariantA),
+    solver!(Two, ex14::Day14VariantA),
+    solver!(One, ex14b::Day14VariantB),
+    solver!(Two, ex14b::Day14VariantB),
+    solver!(One, ex15::Day15VersionA),
+    solver!(One, ex15b::Day15VersionB),
+    solver!(One, ex15c::Day15VersionC),
+    solver!(Two, ex15c::Day15VersionC),
+    solver!(One, ex16::Day16),
+    solver!(One, ex17::Day17),
+    solver!(One, ex17other::Day17other),
+    solver!(One, ex18::Day18),
+    solver!(One, ex19::Day19),
+    solver!(One, ex19petgraph::Day19petgraph),
+    solver!(One, ex20::Day20),
+    solver!(One, ex20b::Day20b),
+    solver!(One, ex21::Day21),
+    solver!(One, ex22::Day22),
+    solver!(One, ex23::Day23),
+    solver!(One, ex23b::Day23b),
+    solver!(One, ex24::Day24),
+    solver!(One, ex24pdo::Day24b),
+    solver!(One, ex25::Day25),
+    solver!(One, ex25b::Day25b),
+];
+
+// -----------------------------------------------------------------------------
+
+/// Solvers of given day (all days when `None`), optionally narrowed to one part and variant.
+pub fn find<'a>(
+    day: Option<DayNum>,
+    part: Option<Part>,
+    variant: Option<&'a str>,
+) -> impl Iterator<Item = &'static SolverEntry> + 'a
+{
+    SOLVERS.iter().filter(move |entry| {
+        day.is_none_or(|day| entry.day == day)
+            && part.is_none_or(|part| entry.part == part)
+            && variant.is_none_or(|variant| entry.variant == variant)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostic::Diagnostic;
+    use aoc_utils::DayInfo;
+    use test_case::test_case;
+
+    struct DayXX;
+
+    impl DaySolver for DayXX {
+        type Output = usize;
+
+        const INFO: DayInfo = DayInfo::with_day_and_file("day_xx", "data_files/day_xx.txt");
+
+        fn solution(s: &str) -> anyhow::Result<<Self as DaySolver>::Output> {
+            Ok(s.trim().len())
+        }
+    }
+
+    impl PartTwo for DayXX {
+        type OutputPartTwo = String;
+
+        fn solution_part_two(s: &str) -> anyhow::Result<Self::OutputPartTwo> {
+            Ok(s.trim().to_uppercase())
+        }
+    }
+
+    #[test]
+    fn registry_keys_are_unique() {
+        for (index, entry) in SOLVERS.iter().enumerate() {
+            assert!(
+                !SOLVERS[index + 1..].iter().any(|other| other.day == entry.day
+                    && other.part == entry.part
+                    && other.variant == entry.variant),
+                "day {} part {} variant `{}` registered twice",
+                entry.day,
+                entry.part,
+                entry.variant
+            );
+        }
+    }
+
+    #[test_case(Some(15), Some(Part::One), None => 3; "all variants of one day")]
+    #[test_case(Some(15), Some(Part::One), Some("hash map") => 1; "single variant")]
+    #[test_case(Some(15), None, Some("unknown") => 0; "unknown variant")]
+    #[test_case(Some(26), None, None => 0; "day without solver")]
+    #[test_case(Some(11), Some(Part::Two), None => 2; "part two variants")]
+    #[test_case(None, None, Some("petgraph") => 1; "variant name across days")]
+    fn registry_find(day: Option<DayNum>, part: Option<Part>, variant: Option<&str>) -> usize {
+        find(day, part, variant).count()
+    }
+
+    #[test]
+    fn registry_solve_default_file() {
+        let entry = find(Some(18), Some(Part::One), None).next().unwrap();
+        assert_eq!(entry.solve_default_file().unwrap(), "26335");
+    }
+
+    #[test]
+    fn registry_solve_input() {
+        let entry = find(Some(18), Some(Part::One), None).next().unwrap();
+        let source = InputSource::Text("2 * 3 + (4 * 5)".to_owned());
+
+        assert_eq!(entry.solve_input(&source).unwrap(), "26");
+        assert!(entry.solve_input(&InputSource::from("aaa")).is_err());
+    }
+
+    #[test]
+    fn registry_solve_input_diagnostic() {
+        let entry = find(Some(8), Some(Part::One), None).next().unwrap();
+        let source = InputSource::Text("nop +0\nabc +1".to_owned());
+
+        let err = entry.solve_input(&source).unwrap_err();
+        let diagnostic = err.downcast_ref::<Diagnostic>().unwrap();
+
+        assert_eq!(diagnostic.file.as_deref(), Some("<text>"));
+        assert_eq!((diagnostic.line, diagnostic.column()), (2, 1));
+        assert!(diagnostic.to_string().ends_with("2 | abc +1\n  | ^^^"));
+        assert!(err.downcast_ref::<ex8::OperationError>().is_some());
+    }
+
+    #[test_case("day_7" => 7)]
+    #[test_case("day_25" => 25)]
+    #[test_case("day_xx" => 0)]
+    fn registry_day_number(name: &str) -> DayNum {
+        day_number(name)
+    }
+
+    #[test]
+    fn registry_entry_parts() {
+        let part_one = solver!(One, DayXX);
+        let part_two = solver!(Two, DayXX);
+
+        assert_eq!((part_one.day, part_one.variant), (0, "base"));
+        assert_eq!(part_one.default_file, "data_files/day_xx.txt");
+        assert_eq!(part_one.part, Part::One);
+        assert_eq!(part_one.solve_default_file().unwrap(), "2");
+        assert_eq!(part_two.part, Part::Two);
+        assert_eq!(part_two.solve_default_file().unwrap(), "XX");
+    }
+}